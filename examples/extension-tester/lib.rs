@@ -150,4 +150,44 @@ mod runtime_tester {
               .map_err(|err| Error::PolymeshError(err))
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink_lang as ink;
+        use polymesh_extension::testing::MockRuntime;
+
+        fn setup() -> MockRuntime {
+            let mock = MockRuntime::new();
+            mock.register();
+            mock
+        }
+
+        #[ink::test]
+        fn call_runtime() {
+            let mock = setup();
+            let mut tester = RuntimeTester::new();
+            assert!(tester.call_runtime(vec![7, 1, 42]).is_ok());
+            assert_eq!(mock.calls(), vec![vec![7, 1, 42]]);
+        }
+
+        #[ink::test]
+        fn read_storage() {
+            let mock = setup();
+            mock.insert_storage(vec![1, 2], vec![3, 4]);
+            let tester = RuntimeTester::new();
+            assert_eq!(tester.read_storage(vec![1, 2]).ok(), Some(Some(vec![3, 4])));
+            assert_eq!(tester.read_storage(vec![5]).ok(), Some(None));
+        }
+
+        #[ink::test]
+        fn versions() {
+            let mock = setup();
+            mock.set_spec_version(5_000_000);
+            mock.set_transaction_version(3);
+            let tester = RuntimeTester::new();
+            assert_eq!(tester.get_spec_version().ok(), Some(5_000_000));
+            assert_eq!(tester.get_transaction_version().ok(), Some(3));
+        }
+    }
 }
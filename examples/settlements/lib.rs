@@ -379,4 +379,21 @@ mod settlements {
             Ok(())
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink_lang as ink;
+        use polymesh_extension::testing::MockRuntime;
+
+        // The off-chain engine can't return a `Some` identity (see `MockRuntime`),
+        // so only the missing identity case can be tested.
+        #[ink::test]
+        #[should_panic(expected = "MissingIdentity")]
+        fn new_requires_contract_identity() {
+            let mock = MockRuntime::new();
+            mock.register();
+            Settlements::new(Ticker::default(), Ticker::default());
+        }
+    }
 }
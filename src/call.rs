@@ -0,0 +1,129 @@
+//! Lightweight runtime call builder, for contracts that only need a few calls.
+//!
+//! ```ignore
+//! // `asset.issue(ticker, amount)`
+//! let call = RuntimeCall::new(ASSET_PALLET, ISSUE_CALL).arg(&ticker).arg(&amount);
+//! Self::env().extension().call_runtime(call.into())?;
+//!
+//! // Resolve the indices from the runtime metadata.
+//! let mut resolver = CallIndexResolver::new();
+//! let call = resolver.call("Asset", "issue")?.ok_or(Error::MissingCall)?.arg(&ticker).arg(&amount);
+//! ```
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use scale::{Encode, Output};
+
+use crate::{new_instance, CallIndex, Encoded, PolymeshRuntimeErr};
+
+/// A runtime call encoded as `pallet_index ++ call_index ++ args`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RuntimeCall {
+    pallet_index: u8,
+    call_index: u8,
+    args: Vec<u8>,
+}
+
+impl RuntimeCall {
+    pub fn new(pallet_index: u8, call_index: u8) -> Self {
+        Self {
+            pallet_index,
+            call_index,
+            args: Vec::new(),
+        }
+    }
+
+    pub fn from_index(index: CallIndex) -> Self {
+        Self::new(index.pallet_index, index.call_index)
+    }
+
+    /// Append the next call argument.
+    pub fn arg<T: Encode>(mut self, arg: &T) -> Self {
+        arg.encode_to(&mut self.args);
+        self
+    }
+
+    /// Append already encoded call arguments.
+    pub fn encoded_args(mut self, args: &[u8]) -> Self {
+        self.args.extend_from_slice(args);
+        self
+    }
+
+    pub fn pallet_index(&self) -> u8 {
+        self.pallet_index
+    }
+
+    pub fn call_index(&self) -> u8 {
+        self.call_index
+    }
+
+    pub fn args(&self) -> &[u8] {
+        &self.args
+    }
+}
+
+impl Encode for RuntimeCall {
+    fn size_hint(&self) -> usize {
+        2 + self.args.len()
+    }
+
+    fn encode_to<O: Output + ?Sized>(&self, out: &mut O) {
+        out.push_byte(self.pallet_index);
+        out.push_byte(self.call_index);
+        out.write(&self.args)
+    }
+}
+
+impl From<RuntimeCall> for Encoded {
+    fn from(call: RuntimeCall) -> Self {
+        Self(call.encode())
+    }
+}
+
+/// Resolves call indices by name using the chain extension and caches them.
+///
+/// Indices can change with a runtime upgrade, so don't keep the resolver in contract storage.
+#[derive(Clone, Debug, Default)]
+pub struct CallIndexResolver {
+    cache: BTreeMap<(Vec<u8>, Vec<u8>), Option<CallIndex>>,
+}
+
+impl CallIndexResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the call index of `pallet.call`.  Returns `None` if the runtime doesn't have the call.
+    pub fn resolve(
+        &mut self,
+        pallet: &str,
+        call: &str,
+    ) -> Result<Option<CallIndex>, PolymeshRuntimeErr> {
+        self.resolve_names(pallet.as_bytes(), call.as_bytes())
+    }
+
+    /// Same as `resolve`, with the names as bytes.
+    pub(crate) fn resolve_names(
+        &mut self,
+        pallet: &[u8],
+        call: &[u8],
+    ) -> Result<Option<CallIndex>, PolymeshRuntimeErr> {
+        let key = (pallet.to_vec(), call.to_vec());
+        if let Some(index) = self.cache.get(&key) {
+            return Ok(*index);
+        }
+        let index = new_instance().get_call_index(key.0.clone(), key.1.clone())?;
+        self.cache.insert(key, index);
+        Ok(index)
+    }
+
+    /// Start building the call `pallet.call`.
+    pub fn call(
+        &mut self,
+        pallet: &str,
+        call: &str,
+    ) -> Result<Option<RuntimeCall>, PolymeshRuntimeErr> {
+        Ok(self.resolve(pallet, call)?.map(RuntimeCall::from_index))
+    }
+}
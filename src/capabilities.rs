@@ -0,0 +1,157 @@
+//! Chain extension capability discovery.
+//!
+//! ```ignore
+//! let caps = ExtensionCapabilities::load()?;
+//! if caps.supports(func_ids::CALL_RUNTIME_BATCH) {
+//!     api.call_runtime_batch(calls)?;
+//! } else {
+//!     for call in calls {
+//!         api.call_runtime(call)?;
+//!     }
+//! }
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::{func_ids, new_instance, PolymeshRuntimeErr};
+
+/// Functions supported by runtimes without `get_extension_version`.
+pub const LEGACY_FUNCTIONS: &[u32] = &[
+    func_ids::CALL_RUNTIME,
+    func_ids::READ_STORAGE,
+    func_ids::GET_SPEC_VERSION,
+    func_ids::GET_TRANSACTION_VERSION,
+    func_ids::GET_KEY_DID,
+    func_ids::TWOX_64,
+    func_ids::TWOX_128,
+    func_ids::TWOX_256,
+];
+
+/// Cached chain extension version and supported function ids.
+///
+/// Load it once per message and use it to check for functions that older runtimes don't have.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtensionCapabilities {
+    version: u32,
+    functions: Vec<u32>,
+}
+
+impl ExtensionCapabilities {
+    /// Query the chain extension.
+    ///
+    /// Runtimes that return `UnknownFunctionId` for `get_extension_version` are treated as
+    /// version `0` with only the `LEGACY_FUNCTIONS`.  Other errors are returned.
+    pub fn load() -> Result<Self, PolymeshRuntimeErr> {
+        let api = new_instance();
+        match api.get_extension_version() {
+            Ok(version) => Ok(Self {
+                version,
+                functions: api.supported_functions()?,
+            }),
+            // Older runtimes don't know the function id.
+            Err(PolymeshRuntimeErr::UnknownFunctionId) => Ok(Self::legacy()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Capabilities of a runtime without `get_extension_version`.
+    pub fn legacy() -> Self {
+        Self {
+            version: 0,
+            functions: LEGACY_FUNCTIONS.to_vec(),
+        }
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn functions(&self) -> &[u32] {
+        &self.functions
+    }
+
+    /// Check if the chain extension supports `func_id`.
+    pub fn supports(&self, func_id: u32) -> bool {
+        self.functions.contains(&func_id)
+    }
+
+    /// Returns `UnknownFunctionId` if `func_id` isn't supported.
+    pub fn ensure_supported(&self, func_id: u32) -> Result<(), PolymeshRuntimeErr> {
+        if self.supports(func_id) {
+            Ok(())
+        } else {
+            Err(PolymeshRuntimeErr::UnknownFunctionId)
+        }
+    }
+
+    /// Returns `UnsupportedVersion` if the chain extension is older than `version`.
+    pub fn ensure_version(&self, version: u32) -> Result<(), PolymeshRuntimeErr> {
+        if self.version >= version {
+            Ok(())
+        } else {
+            Err(PolymeshRuntimeErr::UnsupportedVersion)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{MockRuntime, MOCK_EXTENSION_VERSION};
+
+    #[ink_lang::test]
+    fn load() {
+        let mock = MockRuntime::new();
+        mock.register();
+        // Keep the list of function ids shorter than `MAX_OUTPUT_LEN`.
+        let enabled = [
+            func_ids::CALL_RUNTIME,
+            func_ids::READ_STORAGE,
+            func_ids::GET_EXTENSION_VERSION,
+            func_ids::SUPPORTED_FUNCTIONS,
+        ];
+        for func_id in func_ids::ALL {
+            if !enabled.contains(func_id) {
+                mock.disable_function(*func_id);
+            }
+        }
+        let caps = ExtensionCapabilities::load().unwrap();
+        assert_eq!(caps.version(), MOCK_EXTENSION_VERSION);
+        assert_eq!(caps.functions(), &enabled[..]);
+        assert!(caps.supports(func_ids::CALL_RUNTIME));
+        assert!(!caps.supports(func_ids::CALL_RUNTIME_BATCH));
+    }
+
+    #[ink_lang::test]
+    fn load_legacy() {
+        let mock = MockRuntime::new();
+        mock.register();
+        mock.disable_function(func_ids::GET_EXTENSION_VERSION);
+        assert_eq!(
+            ExtensionCapabilities::load(),
+            Ok(ExtensionCapabilities::legacy())
+        );
+    }
+
+    #[ink_lang::test]
+    fn load_propagates_errors() {
+        let mock = MockRuntime::new();
+        mock.register();
+        mock.disable_function(func_ids::SUPPORTED_FUNCTIONS);
+        assert_eq!(
+            ExtensionCapabilities::load(),
+            Err(PolymeshRuntimeErr::UnknownFunctionId)
+        );
+    }
+
+    #[ink_lang::test]
+    fn load_propagates_version_errors() {
+        let mock = MockRuntime::new();
+        mock.register();
+        mock.set_function_error(func_ids::GET_EXTENSION_VERSION, PolymeshRuntimeErr::Unknown);
+        assert_eq!(
+            ExtensionCapabilities::load(),
+            Err(PolymeshRuntimeErr::Unknown)
+        );
+    }
+}
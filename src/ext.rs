@@ -0,0 +1,268 @@
+//! Typed helpers on top of the raw chain extension functions.
+
+use alloc::vec::Vec;
+
+use ink_env::chain_extension::ChainExtensionMethod;
+use ink_env::AccountId;
+use scale::{Decode, DecodeAll};
+
+use crate::{
+    func_ids, hashing, new_instance, Encoded, EncodedRef, KeyRecord, PolymeshRuntimeErr,
+    PolymeshRuntimeInstance,
+};
+
+/// Decode the values returned by `read_storage_batch`.
+///
+/// Implemented for tuples of `Option<T>` (one item per key) and for `Vec<Option<T>>`.
+/// Tuples return `DecodeFailed` if the number of values doesn't match the tuple's length.
+pub trait DecodeStorageBatch: Sized {
+    fn decode_batch(values: Vec<Option<Vec<u8>>>) -> Result<Self, PolymeshRuntimeErr>;
+}
+
+/// Decode a storage value.  Trailing bytes mean the type doesn't match the stored value.
+fn decode_value<T: Decode>(value: Option<Vec<u8>>) -> Result<Option<T>, PolymeshRuntimeErr> {
+    value
+        .map(|value| T::decode_all(&mut &value[..]))
+        .transpose()
+        .map_err(|_| PolymeshRuntimeErr::DecodeFailed)
+}
+
+impl<T: Decode> DecodeStorageBatch for Vec<Option<T>> {
+    fn decode_batch(values: Vec<Option<Vec<u8>>>) -> Result<Self, PolymeshRuntimeErr> {
+        values.into_iter().map(decode_value::<T>).collect()
+    }
+}
+
+macro_rules! impl_decode_storage_batch {
+    ($($ty:ident),+) => {
+        impl<$($ty: Decode),+> DecodeStorageBatch for ($(Option<$ty>,)+) {
+            fn decode_batch(values: Vec<Option<Vec<u8>>>) -> Result<Self, PolymeshRuntimeErr> {
+                let mut values = values.into_iter();
+                let decoded = ($(
+                    decode_value::<$ty>(values.next().ok_or(PolymeshRuntimeErr::DecodeFailed)?)?,
+                )+);
+                // More values than tuple items means the keys don't match the tuple type.
+                if values.next().is_some() {
+                    return Err(PolymeshRuntimeErr::DecodeFailed);
+                }
+                Ok(decoded)
+            }
+        }
+    };
+}
+
+impl_decode_storage_batch!(A);
+impl_decode_storage_batch!(A, B);
+impl_decode_storage_batch!(A, B, C);
+impl_decode_storage_batch!(A, B, C, D);
+impl_decode_storage_batch!(A, B, C, D, E);
+impl_decode_storage_batch!(A, B, C, D, E, F);
+impl_decode_storage_batch!(A, B, C, D, E, F, G);
+impl_decode_storage_batch!(A, B, C, D, E, F, G, H);
+
+/// Typed helpers for `PolymeshRuntimeInstance`.
+///
+/// ```ignore
+/// use polymesh_extension::PolymeshRuntimeExt;
+///
+/// let counter: Option<u64> = Self::env().extension().read_storage_as(key)?;
+/// let (balance, paused): (Option<u128>, Option<bool>) = Self::env()
+///     .extension()
+///     .read_storage_batch_as(vec![balance_key, paused_key])?;
+/// ```
+pub trait PolymeshRuntimeExt: Copy {
+    /// Same as `call_runtime`, but doesn't copy the call.
+    fn call_runtime_ref(self, call: EncodedRef) -> Result<(), PolymeshRuntimeErr>;
+
+    /// Same as `read_storage`, but doesn't copy the key.
+    fn read_storage_ref(self, key: EncodedRef) -> Result<Option<Vec<u8>>, PolymeshRuntimeErr>;
+
+    /// Same as `twox_64`, but calculated in the contract with feature `local-twox`.
+    fn hash_twox_64(self, data: Encoded) -> Result<[u8; 8], PolymeshRuntimeErr>;
+
+    /// Same as `twox_128`, but calculated in the contract with feature `local-twox`.
+    fn hash_twox_128(self, data: Encoded) -> Result<[u8; 16], PolymeshRuntimeErr>;
+
+    /// Same as `twox_256`, but calculated in the contract with feature `local-twox`.
+    fn hash_twox_256(self, data: Encoded) -> Result<[u8; 32], PolymeshRuntimeErr>;
+
+    /// Read a storage value and decode it.
+    fn read_storage_as<T: Decode>(self, key: Encoded) -> Result<Option<T>, PolymeshRuntimeErr>;
+
+    /// Read a batch of storage values in one call and decode them.
+    fn read_storage_batch_as<T: DecodeStorageBatch>(
+        self,
+        keys: Vec<Encoded>,
+    ) -> Result<T, PolymeshRuntimeErr>;
+
+    /// Get the key's identity.  MultiSig signers are resolved to the MultiSig's identity.
+    fn get_key_identity(self, key: AccountId) -> Result<Option<[u8; 32]>, PolymeshRuntimeErr>;
+}
+
+/// Call a chain extension function with a borrowed input.
+fn call_with_ref<O: Decode>(func_id: u32, input: EncodedRef) -> Result<O, PolymeshRuntimeErr> {
+    ChainExtensionMethod::build(func_id)
+        .input::<EncodedRef>()
+        .output::<O>()
+        .handle_error_code::<PolymeshRuntimeErr>()
+        .call(&input)
+}
+
+impl PolymeshRuntimeExt for PolymeshRuntimeInstance {
+    fn call_runtime_ref(self, call: EncodedRef) -> Result<(), PolymeshRuntimeErr> {
+        call_with_ref(func_ids::CALL_RUNTIME, call)
+    }
+
+    fn read_storage_ref(self, key: EncodedRef) -> Result<Option<Vec<u8>>, PolymeshRuntimeErr> {
+        call_with_ref(func_ids::READ_STORAGE, key)
+    }
+
+    fn hash_twox_64(self, data: Encoded) -> Result<[u8; 8], PolymeshRuntimeErr> {
+        if cfg!(feature = "local-twox") {
+            Ok(hashing::twox_64(&data.0))
+        } else {
+            self.twox_64(data)
+        }
+    }
+
+    fn hash_twox_128(self, data: Encoded) -> Result<[u8; 16], PolymeshRuntimeErr> {
+        if cfg!(feature = "local-twox") {
+            Ok(hashing::twox_128(&data.0))
+        } else {
+            self.twox_128(data)
+        }
+    }
+
+    fn hash_twox_256(self, data: Encoded) -> Result<[u8; 32], PolymeshRuntimeErr> {
+        if cfg!(feature = "local-twox") {
+            Ok(hashing::twox_256(&data.0))
+        } else {
+            self.twox_256(data)
+        }
+    }
+
+    fn read_storage_as<T: Decode>(self, key: Encoded) -> Result<Option<T>, PolymeshRuntimeErr> {
+        decode_value(self.read_storage(key)?)
+    }
+
+    fn read_storage_batch_as<T: DecodeStorageBatch>(
+        self,
+        keys: Vec<Encoded>,
+    ) -> Result<T, PolymeshRuntimeErr> {
+        let keys = keys.into_iter().map(|key| key.0).collect();
+        T::decode_batch(self.read_storage_batch(keys)?)
+    }
+
+    fn get_key_identity(self, key: AccountId) -> Result<Option<[u8; 32]>, PolymeshRuntimeErr> {
+        key_identity(key, |key| new_instance().get_key_record(key))
+    }
+}
+
+/// Get the key's identity from its record.  MultiSig signers are resolved to the MultiSig's identity.
+fn key_identity<F>(
+    key: AccountId,
+    get_key_record: F,
+) -> Result<Option<[u8; 32]>, PolymeshRuntimeErr>
+where
+    F: Fn(AccountId) -> Result<Option<KeyRecord>, PolymeshRuntimeErr>,
+{
+    match get_key_record(key)? {
+        Some(KeyRecord::MultiSigSignerKey(multisig)) => {
+            Ok(get_key_record(multisig)?.and_then(|record| record.did()))
+        }
+        record => Ok(record.and_then(|record| record.did())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scale::Encode;
+
+    type Batch = (Option<u32>, Option<bool>);
+
+    #[test]
+    fn decode_exact_batch() {
+        let values = vec![Some(7u32.encode()), None];
+        assert_eq!(Batch::decode_batch(values), Ok((Some(7), None)));
+    }
+
+    #[test]
+    fn decode_short_batch() {
+        let values = vec![Some(7u32.encode())];
+        assert_eq!(
+            Batch::decode_batch(values),
+            Err(PolymeshRuntimeErr::DecodeFailed)
+        );
+    }
+
+    #[test]
+    fn decode_long_batch() {
+        let values = vec![Some(7u32.encode()), Some(true.encode()), None];
+        assert_eq!(
+            Batch::decode_batch(values),
+            Err(PolymeshRuntimeErr::DecodeFailed)
+        );
+    }
+
+    #[test]
+    fn decode_long_value() {
+        // A `u64` value decoded as `u32`.
+        assert_eq!(
+            decode_value::<u32>(Some(7u64.encode())),
+            Err(PolymeshRuntimeErr::DecodeFailed)
+        );
+        assert_eq!(decode_value::<u64>(Some(7u64.encode())), Ok(Some(7)));
+        assert_eq!(decode_value::<u64>(None), Ok(None));
+    }
+
+    #[test]
+    fn decode_vec_batch() {
+        let values = vec![Some(7u32.encode()), None, Some(8u32.encode())];
+        assert_eq!(
+            Vec::<Option<u32>>::decode_batch(values),
+            Ok(vec![Some(7), None, Some(8)])
+        );
+    }
+
+    #[ink_lang::test]
+    fn hash_twox() {
+        // Uses the mock without feature `local-twox`.
+        crate::testing::MockRuntime::new().register();
+        let data = b"System".to_vec();
+        assert_eq!(
+            new_instance().hash_twox_64(Encoded(data.clone())),
+            Ok(hashing::twox_64(&data))
+        );
+        assert_eq!(
+            new_instance().hash_twox_128(Encoded(data.clone())),
+            Ok(hashing::twox_128(&data))
+        );
+    }
+
+    #[test]
+    fn key_identity_follows_multisig() {
+        // Key records are longer than the off-chain engine's `MAX_OUTPUT_LEN`, so call the mock directly.
+        let mock = crate::testing::MockRuntime::new();
+        let get_key_record = |key: AccountId| -> Result<Option<KeyRecord>, PolymeshRuntimeErr> {
+            let output = mock.call_direct(func_ids::GET_KEY_RECORD, &key.encode())?;
+            Ok(Decode::decode(&mut &output[..]).unwrap())
+        };
+        let primary = AccountId::from([1; 32]);
+        let signer = AccountId::from([2; 32]);
+        let multisig = AccountId::from([3; 32]);
+        mock.set_key_record(primary, KeyRecord::PrimaryKey([4; 32]));
+        mock.set_key_record(signer, KeyRecord::MultiSigSignerKey(multisig));
+        assert_eq!(key_identity(primary, get_key_record), Ok(Some([4; 32])));
+        // The MultiSig doesn't have a record.
+        assert_eq!(key_identity(signer, get_key_record), Ok(None));
+
+        let permissions = crate::Permissions {
+            asset: crate::SubsetRestriction::Whole,
+            extrinsic: crate::SubsetRestriction::Whole,
+            portfolio: crate::SubsetRestriction::Whole,
+        };
+        mock.set_key_record(multisig, KeyRecord::SecondaryKey([5; 32], permissions));
+        assert_eq!(key_identity(signer, get_key_record), Ok(Some([5; 32])));
+    }
+}
@@ -0,0 +1,70 @@
+//! Chain extension function ids.  Must match the ids in the `PolymeshRuntime` trait.
+
+pub const CALL_RUNTIME: u32 = 0x00_00_00_01;
+pub const READ_STORAGE: u32 = 0x00_00_00_02;
+pub const GET_SPEC_VERSION: u32 = 0x00_00_00_03;
+pub const GET_TRANSACTION_VERSION: u32 = 0x00_00_00_04;
+pub const GET_KEY_DID: u32 = 0x00_00_00_05;
+pub const CALL_RUNTIME_WITH_ERROR: u32 = 0x00_00_00_06;
+pub const CALL_RUNTIME_WITH_INFO: u32 = 0x00_00_00_07;
+pub const DRY_RUN_CALL: u32 = 0x00_00_00_08;
+pub const CALL_RUNTIME_BATCH: u32 = 0x00_00_00_09;
+pub const TWOX_64: u32 = 0x00_00_00_10;
+pub const TWOX_128: u32 = 0x00_00_00_11;
+pub const TWOX_256: u32 = 0x00_00_00_12;
+pub const BLAKE2_128: u32 = 0x00_00_00_13;
+pub const BLAKE2_256: u32 = 0x00_00_00_14;
+pub const KECCAK_256: u32 = 0x00_00_00_15;
+pub const SHA2_256: u32 = 0x00_00_00_16;
+pub const SR25519_VERIFY: u32 = 0x00_00_00_20;
+pub const ED25519_VERIFY: u32 = 0x00_00_00_21;
+pub const ECDSA_VERIFY: u32 = 0x00_00_00_22;
+pub const ECDSA_RECOVER: u32 = 0x00_00_00_23;
+pub const READ_STORAGE_RANGE: u32 = 0x00_00_00_30;
+pub const STORAGE_LEN: u32 = 0x00_00_00_31;
+pub const NEXT_STORAGE_KEY: u32 = 0x00_00_00_32;
+pub const READ_STORAGE_BATCH: u32 = 0x00_00_00_33;
+pub const GET_CALL_INDEX: u32 = 0x00_00_00_40;
+pub const GET_RUNTIME_VERSION: u32 = 0x00_00_00_41;
+pub const GET_EXTENSION_VERSION: u32 = 0x00_00_00_42;
+pub const SUPPORTED_FUNCTIONS: u32 = 0x00_00_00_43;
+pub const GET_KEY_RECORD: u32 = 0x00_00_00_50;
+pub const GET_PRIMARY_KEY: u32 = 0x00_00_00_51;
+pub const GET_SECONDARY_KEYS: u32 = 0x00_00_00_52;
+pub const CHECK_PERMISSIONS: u32 = 0x00_00_00_53;
+
+/// All function ids, in order.
+pub const ALL: &[u32] = &[
+    CALL_RUNTIME,
+    READ_STORAGE,
+    GET_SPEC_VERSION,
+    GET_TRANSACTION_VERSION,
+    GET_KEY_DID,
+    CALL_RUNTIME_WITH_ERROR,
+    CALL_RUNTIME_WITH_INFO,
+    DRY_RUN_CALL,
+    CALL_RUNTIME_BATCH,
+    TWOX_64,
+    TWOX_128,
+    TWOX_256,
+    BLAKE2_128,
+    BLAKE2_256,
+    KECCAK_256,
+    SHA2_256,
+    SR25519_VERIFY,
+    ED25519_VERIFY,
+    ECDSA_VERIFY,
+    ECDSA_RECOVER,
+    READ_STORAGE_RANGE,
+    STORAGE_LEN,
+    NEXT_STORAGE_KEY,
+    READ_STORAGE_BATCH,
+    GET_CALL_INDEX,
+    GET_RUNTIME_VERSION,
+    GET_EXTENSION_VERSION,
+    SUPPORTED_FUNCTIONS,
+    GET_KEY_RECORD,
+    GET_PRIMARY_KEY,
+    GET_SECONDARY_KEYS,
+    CHECK_PERMISSIONS,
+];
@@ -0,0 +1,192 @@
+//! Runtime version guard for contracts that depend on a runtime's call and storage layout.
+//!
+//! ```ignore
+//! /// Runtime versions this contract was tested with.
+//! pub const SUPPORTED_VERSIONS: VersionGuard = VersionGuard::new(5_000_000, 5_001_999);
+//!
+//! let api = SUPPORTED_VERSIONS.runtime()?;
+//! api.call_runtime(call)?;
+//! ```
+
+use alloc::vec::Vec;
+
+use scale::Decode;
+
+use crate::{
+    new_instance, DispatchResult, Encoded, PolymeshRuntimeErr, PolymeshRuntimeExt,
+    PolymeshRuntimeInstance,
+};
+
+/// Supported range of runtime spec and transaction versions (inclusive).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VersionGuard {
+    min_spec_version: u32,
+    max_spec_version: u32,
+    min_transaction_version: u32,
+    max_transaction_version: u32,
+}
+
+impl VersionGuard {
+    /// Support spec versions `min..=max` and any transaction version.
+    pub const fn new(min_spec_version: u32, max_spec_version: u32) -> Self {
+        Self {
+            min_spec_version,
+            max_spec_version,
+            min_transaction_version: 0,
+            max_transaction_version: u32::MAX,
+        }
+    }
+
+    /// Only support transaction versions `min..=max`.
+    pub const fn with_transaction_version(mut self, min: u32, max: u32) -> Self {
+        self.min_transaction_version = min;
+        self.max_transaction_version = max;
+        self
+    }
+
+    pub fn is_supported(&self, spec_version: u32, transaction_version: u32) -> bool {
+        (self.min_spec_version..=self.max_spec_version).contains(&spec_version)
+            && (self.min_transaction_version..=self.max_transaction_version)
+                .contains(&transaction_version)
+    }
+
+    /// Returns `UnsupportedSpecVersion` if the runtime's version is outside the supported range.
+    pub fn check(&self) -> Result<(), PolymeshRuntimeErr> {
+        let api = new_instance();
+        let spec_version = api.get_spec_version()?;
+        // Only query the transaction version if it is restricted.
+        let transaction_version =
+            if self.min_transaction_version == 0 && self.max_transaction_version == u32::MAX {
+                0
+            } else {
+                api.get_transaction_version()?
+            };
+        if self.is_supported(spec_version, transaction_version) {
+            Ok(())
+        } else {
+            Err(PolymeshRuntimeErr::UnsupportedSpecVersion)
+        }
+    }
+
+    /// Check the runtime version and return the guarded chain extension.
+    pub fn runtime(&self) -> Result<GuardedRuntime, PolymeshRuntimeErr> {
+        self.check()?;
+        Ok(GuardedRuntime {
+            api: new_instance(),
+        })
+    }
+}
+
+/// Chain extension for a runtime that passed a `VersionGuard` check.
+///
+/// Only use it during the message that created it, the runtime can be upgraded between messages.
+#[derive(Clone, Copy)]
+pub struct GuardedRuntime {
+    api: PolymeshRuntimeInstance,
+}
+
+impl GuardedRuntime {
+    /// The unguarded chain extension.
+    pub fn api(&self) -> PolymeshRuntimeInstance {
+        self.api
+    }
+
+    pub fn call_runtime(&self, call: Encoded) -> Result<(), PolymeshRuntimeErr> {
+        self.api.call_runtime(call)
+    }
+
+    pub fn call_runtime_with_error(
+        &self,
+        call: Encoded,
+    ) -> Result<DispatchResult, PolymeshRuntimeErr> {
+        self.api.call_runtime_with_error(call)
+    }
+
+    pub fn read_storage(&self, key: Encoded) -> Result<Option<Vec<u8>>, PolymeshRuntimeErr> {
+        self.api.read_storage(key)
+    }
+
+    pub fn read_storage_as<T: Decode>(
+        &self,
+        key: Encoded,
+    ) -> Result<Option<T>, PolymeshRuntimeErr> {
+        self.api.read_storage_as(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::func_ids;
+    use crate::testing::MockRuntime;
+
+    const GUARD: VersionGuard = VersionGuard::new(5_000_000, 5_001_999);
+
+    fn register(spec_version: u32, transaction_version: u32) -> MockRuntime {
+        let mock = MockRuntime::new();
+        mock.register();
+        mock.set_spec_version(spec_version);
+        mock.set_transaction_version(transaction_version);
+        mock
+    }
+
+    #[ink_lang::test]
+    fn in_range() {
+        let mock = register(5_000_000, 1);
+        assert_eq!(GUARD.check(), Ok(()));
+        mock.set_spec_version(5_001_999);
+        assert_eq!(GUARD.check(), Ok(()));
+    }
+
+    #[ink_lang::test]
+    fn spec_version_out_of_range() {
+        let mock = register(4_999_999, 1);
+        assert_eq!(
+            GUARD.check(),
+            Err(PolymeshRuntimeErr::UnsupportedSpecVersion)
+        );
+        mock.set_spec_version(5_002_000);
+        assert_eq!(
+            GUARD.check(),
+            Err(PolymeshRuntimeErr::UnsupportedSpecVersion)
+        );
+    }
+
+    #[ink_lang::test]
+    fn unrestricted_transaction_version_is_not_queried() {
+        let mock = register(5_000_000, 1);
+        mock.disable_function(func_ids::GET_TRANSACTION_VERSION);
+        assert_eq!(GUARD.check(), Ok(()));
+    }
+
+    #[ink_lang::test]
+    fn transaction_version_out_of_range() {
+        let guard = GUARD.with_transaction_version(2, 3);
+        let mock = register(5_000_000, 1);
+        assert_eq!(
+            guard.check(),
+            Err(PolymeshRuntimeErr::UnsupportedSpecVersion)
+        );
+        mock.set_transaction_version(4);
+        assert_eq!(
+            guard.check(),
+            Err(PolymeshRuntimeErr::UnsupportedSpecVersion)
+        );
+        mock.set_transaction_version(3);
+        assert_eq!(guard.check(), Ok(()));
+    }
+
+    #[ink_lang::test]
+    fn runtime_is_guarded() {
+        let mock = register(4_999_999, 1);
+        mock.insert_storage(vec![1], vec![2]);
+        assert!(matches!(
+            GUARD.runtime(),
+            Err(PolymeshRuntimeErr::UnsupportedSpecVersion)
+        ));
+
+        mock.set_spec_version(5_000_000);
+        let api = GUARD.runtime().unwrap();
+        assert_eq!(api.read_storage(Encoded(vec![1])), Ok(Some(vec![2])));
+    }
+}
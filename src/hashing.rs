@@ -0,0 +1,205 @@
+//! `const fn` xxhash (twox) hashing, to calculate storage prefixes at compile time.
+//! Also used by `PolymeshRuntimeExt::hash_twox_*` when the `local-twox` feature is enabled.
+//!
+//! ```ignore
+//! const BALANCE_OF: StorageDoubleMap<Blake2_128Concat, Blake2_128Concat> =
+//!     StorageDoubleMap::from_prefix(storage_prefix!("Asset", "BalanceOf"));
+//! ```
+
+const PRIME64_1: u64 = 0x9E3779B185EBCA87;
+const PRIME64_2: u64 = 0xC2B2AE3D27D4EB4F;
+const PRIME64_3: u64 = 0x165667B19E3779F9;
+const PRIME64_4: u64 = 0x85EBCA77C2B2AE63;
+const PRIME64_5: u64 = 0x27D4EB2F165667C5;
+
+const fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes([
+        data[offset],
+        data[offset + 1],
+        data[offset + 2],
+        data[offset + 3],
+        data[offset + 4],
+        data[offset + 5],
+        data[offset + 6],
+        data[offset + 7],
+    ])
+}
+
+const fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        data[offset],
+        data[offset + 1],
+        data[offset + 2],
+        data[offset + 3],
+    ])
+}
+
+const fn round(acc: u64, input: u64) -> u64 {
+    acc.wrapping_add(input.wrapping_mul(PRIME64_2))
+        .rotate_left(31)
+        .wrapping_mul(PRIME64_1)
+}
+
+const fn merge_round(acc: u64, val: u64) -> u64 {
+    (acc ^ round(0, val))
+        .wrapping_mul(PRIME64_1)
+        .wrapping_add(PRIME64_4)
+}
+
+/// XXH64 hash of `data`.
+pub const fn xxh64(data: &[u8], seed: u64) -> u64 {
+    let len = data.len();
+    let mut offset = 0;
+    let mut hash;
+
+    if len >= 32 {
+        let mut v1 = seed.wrapping_add(PRIME64_1).wrapping_add(PRIME64_2);
+        let mut v2 = seed.wrapping_add(PRIME64_2);
+        let mut v3 = seed;
+        let mut v4 = seed.wrapping_sub(PRIME64_1);
+        while offset + 32 <= len {
+            v1 = round(v1, read_u64(data, offset));
+            v2 = round(v2, read_u64(data, offset + 8));
+            v3 = round(v3, read_u64(data, offset + 16));
+            v4 = round(v4, read_u64(data, offset + 24));
+            offset += 32;
+        }
+        hash = v1
+            .rotate_left(1)
+            .wrapping_add(v2.rotate_left(7))
+            .wrapping_add(v3.rotate_left(12))
+            .wrapping_add(v4.rotate_left(18));
+        hash = merge_round(hash, v1);
+        hash = merge_round(hash, v2);
+        hash = merge_round(hash, v3);
+        hash = merge_round(hash, v4);
+    } else {
+        hash = seed.wrapping_add(PRIME64_5);
+    }
+
+    hash = hash.wrapping_add(len as u64);
+
+    while offset + 8 <= len {
+        hash ^= round(0, read_u64(data, offset));
+        hash = hash
+            .rotate_left(27)
+            .wrapping_mul(PRIME64_1)
+            .wrapping_add(PRIME64_4);
+        offset += 8;
+    }
+    if offset + 4 <= len {
+        hash ^= (read_u32(data, offset) as u64).wrapping_mul(PRIME64_1);
+        hash = hash
+            .rotate_left(23)
+            .wrapping_mul(PRIME64_2)
+            .wrapping_add(PRIME64_3);
+        offset += 4;
+    }
+    while offset < len {
+        hash ^= (data[offset] as u64).wrapping_mul(PRIME64_5);
+        hash = hash.rotate_left(11).wrapping_mul(PRIME64_1);
+        offset += 1;
+    }
+
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(PRIME64_2);
+    hash ^= hash >> 29;
+    hash = hash.wrapping_mul(PRIME64_3);
+    hash ^= hash >> 32;
+    hash
+}
+
+/// Concatenate the little-endian XXH64 hashes of `data` using seeds `0..N/8`.
+const fn twox<const N: usize>(data: &[u8]) -> [u8; N] {
+    let mut out = [0u8; N];
+    let mut seed = 0;
+    while seed < N / 8 {
+        let hash = xxh64(data, seed as u64).to_le_bytes();
+        let mut i = 0;
+        while i < 8 {
+            out[seed * 8 + i] = hash[i];
+            i += 1;
+        }
+        seed += 1;
+    }
+    out
+}
+
+pub const fn twox_64(data: &[u8]) -> [u8; 8] {
+    twox::<8>(data)
+}
+
+pub const fn twox_128(data: &[u8]) -> [u8; 16] {
+    twox::<16>(data)
+}
+
+pub const fn twox_256(data: &[u8]) -> [u8; 32] {
+    twox::<32>(data)
+}
+
+/// Calculate the storage prefix `twox_128(pallet) ++ twox_128(item)`.
+pub const fn storage_prefix(pallet: &str, item: &str) -> [u8; 32] {
+    let pallet = twox_128(pallet.as_bytes());
+    let item = twox_128(item.as_bytes());
+    let mut prefix = [0u8; 32];
+    let mut i = 0;
+    while i < 16 {
+        prefix[i] = pallet[i];
+        prefix[16 + i] = item[i];
+        i += 1;
+    }
+    prefix
+}
+
+/// Calculate a storage prefix at compile time.
+///
+/// `storage_prefix!("Asset", "BalanceOf")`
+#[macro_export]
+macro_rules! storage_prefix {
+    ($pallet:expr, $item:expr) => {{
+        const PREFIX: [u8; 32] = $crate::hashing::storage_prefix($pallet, $item);
+        PREFIX
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn twox_matches_sp_core_hashing() {
+        // Covers the 4, 8 and 32 byte block boundaries and the empty input.
+        for len in 0..=100 {
+            let data = (0..len).map(|i| (i * 7 + 3) as u8).collect::<Vec<_>>();
+            assert_eq!(
+                twox_64(&data),
+                sp_core_hashing::twox_64(&data),
+                "len {}",
+                len
+            );
+            assert_eq!(
+                twox_128(&data),
+                sp_core_hashing::twox_128(&data),
+                "len {}",
+                len
+            );
+            assert_eq!(
+                twox_256(&data),
+                sp_core_hashing::twox_256(&data),
+                "len {}",
+                len
+            );
+        }
+    }
+
+    #[test]
+    fn system_account_prefix() {
+        const SYSTEM_ACCOUNT: [u8; 32] = [
+            0x26, 0xaa, 0x39, 0x4e, 0xea, 0x56, 0x30, 0xe0, 0x7c, 0x48, 0xae, 0x0c, 0x95, 0x58,
+            0xce, 0xf7, 0xb9, 0x9d, 0x88, 0x0e, 0xc6, 0x81, 0x79, 0x9c, 0x0c, 0xf3, 0x0e, 0x88,
+            0x86, 0x37, 0x1d, 0xa9,
+        ];
+        assert_eq!(storage_prefix("System", "Account"), SYSTEM_ACCOUNT);
+        assert_eq!(crate::storage_prefix!("System", "Account"), SYSTEM_ACCOUNT);
+    }
+}
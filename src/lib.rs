@@ -12,6 +12,31 @@ use scale_info::TypeInfo;
 
 use alloc::vec::Vec;
 
+mod types;
+pub use types::*;
+
+pub mod func_ids;
+
+mod call;
+pub use call::*;
+
+mod capabilities;
+pub use capabilities::*;
+
+mod guard;
+pub use guard::*;
+
+mod permissions;
+
+pub mod hashing;
+pub mod storage;
+
+mod ext;
+pub use ext::*;
+
+#[cfg(feature = "std")]
+pub mod testing;
+
 /// `Encoded` is used to avoid encoding an extra length that isn't needed.
 pub struct Encoded(pub Vec<u8>);
 
@@ -37,6 +62,38 @@ impl Encode for Encoded {
     }
 }
 
+/// Borrowed version of `Encoded`, to pass constant or precomputed data without copying it.
+#[derive(Clone, Copy)]
+pub struct EncodedRef<'a>(pub &'a [u8]);
+
+impl<'a> From<&'a [u8]> for EncodedRef<'a> {
+    fn from(data: &'a [u8]) -> Self {
+        Self(data)
+    }
+}
+
+impl<'a, const N: usize> From<&'a [u8; N]> for EncodedRef<'a> {
+    fn from(data: &'a [u8; N]) -> Self {
+        Self(&data[..])
+    }
+}
+
+impl<'a> From<&'a Encoded> for EncodedRef<'a> {
+    fn from(data: &'a Encoded) -> Self {
+        Self(&data.0)
+    }
+}
+
+impl Encode for EncodedRef<'_> {
+    fn size_hint(&self) -> usize {
+        self.0.len()
+    }
+
+    fn encode_to<O: Output + ?Sized>(&self, out: &mut O) {
+        out.write(self.0)
+    }
+}
+
 #[ink::chain_extension]
 #[derive(Clone, Copy)]
 pub trait PolymeshRuntime {
@@ -57,6 +114,24 @@ pub trait PolymeshRuntime {
     #[ink(extension = 0x00_00_00_05, returns_result = false)]
     fn get_key_did(key: AccountId) -> Option<[u8; 32]>;
 
+    /// Same as `call_runtime`, but returns the `DispatchError` if the call fails.
+    #[ink(extension = 0x00_00_00_06, returns_result = false)]
+    fn call_runtime_with_error(call: Encoded) -> DispatchResult;
+
+    /// Same as `call_runtime_with_error`, but also returns the actual weight and fee info.
+    #[ink(extension = 0x00_00_00_07, returns_result = false)]
+    fn call_runtime_with_info(call: Encoded) -> CallInfo;
+
+    /// Dispatch a runtime call in a transactional layer that is always rolled back.
+    /// Returns the result the call would have and the weight used.
+    #[ink(extension = 0x00_00_00_08, returns_result = false)]
+    fn dry_run_call(call: Encoded) -> CallInfo;
+
+    /// Dispatch all calls or none of them (like `utility.batch_all`).
+    /// Returns the index of the first failing call.
+    #[ink(extension = 0x00_00_00_09, returns_result = false)]
+    fn call_runtime_batch(calls: Vec<Encoded>) -> BatchResult;
+
     #[ink(extension = 0x00_00_00_10, returns_result = false)]
     fn twox_64(data: Encoded) -> [u8; 8];
 
@@ -65,6 +140,90 @@ pub trait PolymeshRuntime {
 
     #[ink(extension = 0x00_00_00_12, returns_result = false)]
     fn twox_256(data: Encoded) -> [u8; 32];
+
+    #[ink(extension = 0x00_00_00_13, returns_result = false)]
+    fn blake2_128(data: Encoded) -> [u8; 16];
+
+    #[ink(extension = 0x00_00_00_14, returns_result = false)]
+    fn blake2_256(data: Encoded) -> [u8; 32];
+
+    #[ink(extension = 0x00_00_00_15, returns_result = false)]
+    fn keccak_256(data: Encoded) -> [u8; 32];
+
+    #[ink(extension = 0x00_00_00_16, returns_result = false)]
+    fn sha2_256(data: Encoded) -> [u8; 32];
+
+    /// Verify a sr25519 signature.  The message is last, since `Encoded` doesn't have a length prefix.
+    #[ink(extension = 0x00_00_00_20, returns_result = false)]
+    fn sr25519_verify(signature: [u8; 64], public: [u8; 32], message: Encoded) -> bool;
+
+    /// Verify an ed25519 signature.
+    #[ink(extension = 0x00_00_00_21, returns_result = false)]
+    fn ed25519_verify(signature: [u8; 64], public: [u8; 32], message: Encoded) -> bool;
+
+    /// Verify an ecdsa signature.  The message is hashed with `blake2_256`.
+    #[ink(extension = 0x00_00_00_22, returns_result = false)]
+    fn ecdsa_verify(signature: [u8; 65], public: [u8; 33], message: Encoded) -> bool;
+
+    /// Recover the compressed ecdsa public key from a signature and a 32 byte message hash.
+    #[ink(extension = 0x00_00_00_23, returns_result = false)]
+    fn ecdsa_recover(signature: [u8; 65], message_hash: [u8; 32]) -> Option<[u8; 33]>;
+
+    /// Read at most `len` bytes of a storage value starting at `offset`.
+    /// The key is last, since `Encoded` doesn't have a length prefix.
+    #[ink(extension = 0x00_00_00_30, returns_result = false)]
+    fn read_storage_range(offset: u32, len: u32, key: Encoded) -> Option<Vec<u8>>;
+
+    /// Get the size of a storage value without reading it.
+    #[ink(extension = 0x00_00_00_31, returns_result = false)]
+    fn storage_len(key: Encoded) -> Option<u32>;
+
+    /// Get the next storage key after `start_after` that starts with `prefix`.
+    /// If `start_after` is `None` the first key with `prefix` is returned.
+    #[ink(extension = 0x00_00_00_32, returns_result = false)]
+    fn next_storage_key(prefix: Vec<u8>, start_after: Option<Vec<u8>>) -> Option<Vec<u8>>;
+
+    /// Read multiple storage values in one call.  Each key needs a length prefix
+    /// so `Vec<u8>` is used instead of `Encoded`.
+    #[ink(extension = 0x00_00_00_33, returns_result = false)]
+    fn read_storage_batch(keys: Vec<Vec<u8>>) -> Vec<Option<Vec<u8>>>;
+
+    /// Get the pallet and call index of `pallet_name.call_name` from the runtime metadata.
+    #[ink(extension = 0x00_00_00_40, returns_result = false)]
+    fn get_call_index(pallet_name: Vec<u8>, call_name: Vec<u8>) -> Option<CallIndex>;
+
+    /// Get the full runtime version in one call.
+    #[ink(extension = 0x00_00_00_41, returns_result = false)]
+    fn get_runtime_version() -> RuntimeVersion;
+
+    /// Get the version of the chain extension.
+    #[ink(extension = 0x00_00_00_42, returns_result = false)]
+    fn get_extension_version() -> u32;
+
+    /// Get the function ids supported by the chain extension.
+    #[ink(extension = 0x00_00_00_43, returns_result = false)]
+    fn supported_functions() -> Vec<u32>;
+
+    /// Get the key's full record (like `get_key_did`, but includes secondary key permissions and MultiSig signers).
+    #[ink(extension = 0x00_00_00_50, returns_result = false)]
+    fn get_key_record(key: AccountId) -> Option<KeyRecord>;
+
+    /// Get the primary key of an identity.
+    #[ink(extension = 0x00_00_00_51, returns_result = false)]
+    fn get_primary_key(did: [u8; 32]) -> Option<AccountId>;
+
+    /// Get at most `limit` secondary keys of an identity, ordered by key, starting after `start_after`.
+    #[ink(extension = 0x00_00_00_52, returns_result = false)]
+    fn get_secondary_keys(
+        did: [u8; 32],
+        start_after: Option<AccountId>,
+        limit: u32,
+    ) -> Vec<SecondaryKey>;
+
+    /// Check if `key` has permission for the extrinsic, assets and portfolios.
+    /// Primary keys have all permissions.
+    #[ink(extension = 0x00_00_00_53, returns_result = false)]
+    fn check_permissions(key: AccountId, check: PermissionCheck) -> bool;
 }
 
 pub type PolymeshRuntimeInstance = <PolymeshRuntime as ink::ChainExtensionInstance>::Instance;
@@ -76,15 +235,78 @@ pub fn new_instance() -> PolymeshRuntimeInstance {
 #[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
 #[cfg_attr(feature = "std", derive(TypeInfo))]
 pub enum PolymeshRuntimeErr {
+    /// Unknown error.
     Unknown,
+    /// The runtime call failed to dispatch.
+    CallDispatchFailed,
+    /// The runtime failed to decode the storage value (status code 3).
+    ///
+    /// Returned by the runtime.  Decode failures in the contract use `DecodeFailed`.
+    StorageDecodeFailed,
+    /// The contract doesn't have permission for this call.
+    PermissionDenied,
+    /// The chain extension doesn't support this function id.
+    UnknownFunctionId,
+    /// The input is too large.
+    InputTooLarge,
+    /// The chain extension doesn't support this version.
+    UnsupportedVersion,
+    /// The contract failed to decode a value returned by the chain extension (status code 8).
+    ///
+    /// Never returned by the runtime, only by the typed helpers in this crate.
+    /// Decode failures in the runtime use `StorageDecodeFailed`.
+    DecodeFailed,
+    /// The runtime's version is outside the range supported by the contract.
+    UnsupportedSpecVersion,
+    /// The runtime doesn't have the call, so it wasn't dispatched (status code 10).
+    ///
+    /// Never returned by the runtime, only by the call helpers in this crate.
+    UnknownCall,
+    /// Unknown status code, from a newer runtime.
+    Other(u32),
+}
+
+impl PolymeshRuntimeErr {
+    /// Map a non-zero status code returned by the chain extension to an error.
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => Self::Unknown,
+            2 => Self::CallDispatchFailed,
+            3 => Self::StorageDecodeFailed,
+            4 => Self::PermissionDenied,
+            5 => Self::UnknownFunctionId,
+            6 => Self::InputTooLarge,
+            7 => Self::UnsupportedVersion,
+            8 => Self::DecodeFailed,
+            9 => Self::UnsupportedSpecVersion,
+            10 => Self::UnknownCall,
+            code => Self::Other(code),
+        }
+    }
+
+    /// The status code for this error.
+    pub fn code(&self) -> u32 {
+        match self {
+            Self::Unknown => 1,
+            Self::CallDispatchFailed => 2,
+            Self::StorageDecodeFailed => 3,
+            Self::PermissionDenied => 4,
+            Self::UnknownFunctionId => 5,
+            Self::InputTooLarge => 6,
+            Self::UnsupportedVersion => 7,
+            Self::DecodeFailed => 8,
+            Self::UnsupportedSpecVersion => 9,
+            Self::UnknownCall => 10,
+            Self::Other(code) => *code,
+        }
+    }
 }
 
 impl ink_env::chain_extension::FromStatusCode for PolymeshRuntimeErr {
     fn from_status_code(status_code: u32) -> Result<(), Self> {
         match status_code {
             0 => Ok(()),
-            1 => Err(Self::Unknown),
-            _ => panic!("encountered unknown status code"),
+            code => Err(Self::from_code(code)),
         }
     }
 }
@@ -103,3 +325,37 @@ impl Environment for PolymeshEnvironment {
 
     type ChainExtension = PolymeshRuntime;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ink_env::chain_extension::FromStatusCode;
+
+    #[test]
+    fn status_code_round_trip() {
+        for code in 1..=10 {
+            let err = PolymeshRuntimeErr::from_code(code);
+            assert!(
+                !matches!(err, PolymeshRuntimeErr::Other(_)),
+                "code {}",
+                code
+            );
+            assert_eq!(err.code(), code);
+            assert_eq!(PolymeshRuntimeErr::from_status_code(code), Err(err));
+        }
+    }
+
+    #[test]
+    fn unknown_status_code() {
+        assert_eq!(
+            PolymeshRuntimeErr::from_code(42),
+            PolymeshRuntimeErr::Other(42)
+        );
+        assert_eq!(PolymeshRuntimeErr::Other(42).code(), 42);
+    }
+
+    #[test]
+    fn zero_status_code_is_ok() {
+        assert_eq!(PolymeshRuntimeErr::from_status_code(0), Ok(()));
+    }
+}
@@ -0,0 +1,115 @@
+//! Check a caller's secondary key permissions before acting on their behalf.
+//!
+//! ```ignore
+//! let mut resolver = CallIndexResolver::new();
+//! PermissionCheck::new("Portfolio", "move_portfolio_funds")
+//!     .asset(ticker)
+//!     .portfolio(caller_portfolio)
+//!     .call_runtime_for_caller(&mut resolver, &(from, to, items).encode())?;
+//! ```
+
+use ink_env::AccountId;
+
+use crate::{
+    new_instance, CallIndexResolver, PermissionCheck, PolymeshEnvironment, PolymeshRuntimeErr,
+    RuntimeCall,
+};
+
+impl PermissionCheck {
+    /// Check if `key` has the permissions.
+    pub fn check(&self, key: AccountId) -> Result<bool, PolymeshRuntimeErr> {
+        new_instance().check_permissions(key, self.clone())
+    }
+
+    /// Returns `PermissionDenied` if the contract's caller doesn't have the permissions.
+    pub fn ensure_caller(&self) -> Result<(), PolymeshRuntimeErr> {
+        let caller = ink_env::caller::<PolymeshEnvironment>();
+        if self.check(caller)? {
+            Ok(())
+        } else {
+            Err(PolymeshRuntimeErr::PermissionDenied)
+        }
+    }
+
+    /// Check the caller's permissions, then dispatch the checked extrinsic with the encoded `args`.
+    ///
+    /// The call index is resolved from `pallet_name` and `dispatchable_name`, so only the checked
+    /// extrinsic can be dispatched.  Returns `UnknownCall` if the runtime doesn't have it.
+    ///
+    /// The checked `assets` and `portfolios` are NOT tied to `args`.  The contract must make sure
+    /// `args` only uses the checked assets and portfolios, otherwise a secondary key restricted to
+    /// asset A could move asset B.
+    pub fn call_runtime_for_caller(
+        &self,
+        resolver: &mut CallIndexResolver,
+        args: &[u8],
+    ) -> Result<(), PolymeshRuntimeErr> {
+        self.ensure_caller()?;
+        let index = resolver
+            .resolve_names(&self.pallet_name, &self.dispatchable_name)?
+            .ok_or(PolymeshRuntimeErr::UnknownCall)?;
+        let call = RuntimeCall::from_index(index).encoded_args(args);
+        new_instance().call_runtime(call.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockRuntime;
+    use crate::{KeyRecord, PalletPermissions, Permissions, SubsetRestriction};
+
+    fn setup() -> (MockRuntime, AccountId) {
+        let mock = MockRuntime::new();
+        mock.register();
+        mock.set_call_index("Asset", "issue", 7, 1);
+        mock.set_call_index("Portfolio", "move_portfolio_funds", 8, 2);
+        let caller = ink_env::caller::<PolymeshEnvironment>();
+        let permissions = Permissions {
+            asset: SubsetRestriction::Whole,
+            extrinsic: SubsetRestriction::These(vec![PalletPermissions {
+                pallet_name: b"Asset".to_vec(),
+                dispatchable_names: SubsetRestriction::These(vec![b"issue".to_vec()]),
+            }]),
+            portfolio: SubsetRestriction::Whole,
+        };
+        mock.set_key_record(caller, KeyRecord::SecondaryKey([1; 32], permissions));
+        (mock, caller)
+    }
+
+    #[ink_lang::test]
+    fn dispatches_the_checked_call() {
+        let (mock, _) = setup();
+        let mut resolver = CallIndexResolver::new();
+        let res =
+            PermissionCheck::new("Asset", "issue").call_runtime_for_caller(&mut resolver, &[42]);
+        assert_eq!(res, Ok(()));
+        assert_eq!(mock.calls(), vec![vec![7, 1, 42]]);
+    }
+
+    #[ink_lang::test]
+    fn denies_unpermitted_call() {
+        let (mock, caller) = setup();
+        let check = PermissionCheck::new("Portfolio", "move_portfolio_funds");
+        assert_eq!(check.check(caller), Ok(false));
+        let mut resolver = CallIndexResolver::new();
+        assert_eq!(
+            check.call_runtime_for_caller(&mut resolver, &[42]),
+            Err(PolymeshRuntimeErr::PermissionDenied)
+        );
+        assert!(mock.calls().is_empty());
+    }
+
+    #[ink_lang::test]
+    fn unknown_call_is_not_dispatched() {
+        let (mock, caller) = setup();
+        // Primary keys have all permissions, but the runtime doesn't have `Asset.redeem`.
+        mock.set_key_record(caller, KeyRecord::PrimaryKey([1; 32]));
+        let mut resolver = CallIndexResolver::new();
+        assert_eq!(
+            PermissionCheck::new("Asset", "redeem").call_runtime_for_caller(&mut resolver, &[42]),
+            Err(PolymeshRuntimeErr::UnknownCall)
+        );
+        assert!(mock.calls().is_empty());
+    }
+}
@@ -0,0 +1,429 @@
+//! Storage key builders for `read_storage`.
+//!
+//! Keys are built the same way as FRAME storage keys:
+//! `twox_128(pallet) ++ twox_128(item) ++ hasher(key1) ++ hasher(key2) ...`
+//!
+//! The builders are `const`, so the prefix can be calculated at compile time:
+//!
+//! ```ignore
+//! const VENUE_COUNTER: StorageValue = StorageValue::new("Settlement", "VenueCounter");
+//! ```
+
+use core::marker::PhantomData;
+
+use alloc::vec::Vec;
+
+use ink_env::hash::Blake2x128;
+use scale::{Decode, DecodeAll, Encode};
+
+use crate::hashing::storage_prefix;
+use crate::{new_instance, Encoded, PolymeshRuntimeErr, PolymeshRuntimeExt};
+
+/// Hasher used for map keys.
+pub trait StorageHasher {
+    /// Append the hash of `data` to `key`.
+    fn hash_to(data: &[u8], key: &mut Vec<u8>) -> Result<(), PolymeshRuntimeErr>;
+}
+
+/// Hasher that appends the original key after the hash, so the key can be decoded.
+pub trait ReversibleStorageHasher: StorageHasher {
+    /// Length of the hash before the original key.
+    const HASH_LEN: usize;
+}
+
+/// No hashing, the encoded key is used as-is.
+pub enum Identity {}
+
+impl StorageHasher for Identity {
+    fn hash_to(data: &[u8], key: &mut Vec<u8>) -> Result<(), PolymeshRuntimeErr> {
+        key.extend_from_slice(data);
+        Ok(())
+    }
+}
+
+impl ReversibleStorageHasher for Identity {
+    const HASH_LEN: usize = 0;
+}
+
+/// `twox_64(key) ++ key`.
+pub enum Twox64Concat {}
+
+impl StorageHasher for Twox64Concat {
+    fn hash_to(data: &[u8], key: &mut Vec<u8>) -> Result<(), PolymeshRuntimeErr> {
+        key.extend_from_slice(&new_instance().hash_twox_64(data.to_vec().into())?);
+        key.extend_from_slice(data);
+        Ok(())
+    }
+}
+
+impl ReversibleStorageHasher for Twox64Concat {
+    const HASH_LEN: usize = 8;
+}
+
+/// `twox_128(key)`.
+pub enum Twox128 {}
+
+impl StorageHasher for Twox128 {
+    fn hash_to(data: &[u8], key: &mut Vec<u8>) -> Result<(), PolymeshRuntimeErr> {
+        key.extend_from_slice(&new_instance().hash_twox_128(data.to_vec().into())?);
+        Ok(())
+    }
+}
+
+/// `blake2_128(key) ++ key`.
+#[allow(non_camel_case_types)]
+pub enum Blake2_128Concat {}
+
+impl StorageHasher for Blake2_128Concat {
+    fn hash_to(data: &[u8], key: &mut Vec<u8>) -> Result<(), PolymeshRuntimeErr> {
+        let mut hash = [0u8; 16];
+        ink_env::hash_bytes::<Blake2x128>(data, &mut hash);
+        key.extend_from_slice(&hash);
+        key.extend_from_slice(data);
+        Ok(())
+    }
+}
+
+impl ReversibleStorageHasher for Blake2_128Concat {
+    const HASH_LEN: usize = 16;
+}
+
+/// A storage key under construction.  Used by `StorageNMap`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StorageKey(Vec<u8>);
+
+impl StorageKey {
+    /// Start a new key from a storage prefix.
+    pub fn new(prefix: &[u8]) -> Self {
+        Self(prefix.to_vec())
+    }
+
+    /// Hash and append the next key.
+    pub fn push<H: StorageHasher, K: Encode>(
+        mut self,
+        key: &K,
+    ) -> Result<Self, PolymeshRuntimeErr> {
+        H::hash_to(&key.encode(), &mut self.0)?;
+        Ok(self)
+    }
+
+    pub fn into_inner(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl From<StorageKey> for Encoded {
+    fn from(key: StorageKey) -> Self {
+        Self(key.0)
+    }
+}
+
+/// Key builder for a storage value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StorageValue {
+    prefix: [u8; 32],
+}
+
+impl StorageValue {
+    pub const fn new(pallet: &str, item: &str) -> Self {
+        Self::from_prefix(storage_prefix(pallet, item))
+    }
+
+    pub const fn from_prefix(prefix: [u8; 32]) -> Self {
+        Self { prefix }
+    }
+
+    pub fn prefix(&self) -> [u8; 32] {
+        self.prefix
+    }
+
+    pub fn key(&self) -> Encoded {
+        Encoded(self.prefix.to_vec())
+    }
+}
+
+/// Key builder for a storage map.
+pub struct StorageMap<H> {
+    prefix: [u8; 32],
+    _hasher: PhantomData<H>,
+}
+
+impl<H> StorageMap<H> {
+    pub const fn new(pallet: &str, item: &str) -> Self {
+        Self::from_prefix(storage_prefix(pallet, item))
+    }
+
+    pub const fn from_prefix(prefix: [u8; 32]) -> Self {
+        Self {
+            prefix,
+            _hasher: PhantomData,
+        }
+    }
+
+    pub fn prefix(&self) -> [u8; 32] {
+        self.prefix
+    }
+}
+
+impl<H: StorageHasher> StorageMap<H> {
+    pub fn key<K: Encode>(&self, key: &K) -> Result<Encoded, PolymeshRuntimeErr> {
+        Ok(StorageKey::new(&self.prefix).push::<H, K>(key)?.into())
+    }
+}
+
+impl<H: ReversibleStorageHasher> StorageMap<H> {
+    /// Iterate over at most `limit` keys of the map, starting after the raw key `start_after`.
+    pub fn keys<K: Decode>(
+        &self,
+        start_after: Option<Vec<u8>>,
+        limit: u32,
+    ) -> StorageMapKeys<H, K> {
+        StorageMapKeys {
+            iter: StorageKeyIter::new(self.prefix.to_vec(), start_after, limit),
+            _key: PhantomData,
+        }
+    }
+}
+
+/// Key builder for a storage double map.
+pub struct StorageDoubleMap<H1, H2> {
+    prefix: [u8; 32],
+    _hashers: PhantomData<(H1, H2)>,
+}
+
+impl<H1, H2> StorageDoubleMap<H1, H2> {
+    pub const fn new(pallet: &str, item: &str) -> Self {
+        Self::from_prefix(storage_prefix(pallet, item))
+    }
+
+    pub const fn from_prefix(prefix: [u8; 32]) -> Self {
+        Self {
+            prefix,
+            _hashers: PhantomData,
+        }
+    }
+
+    pub fn prefix(&self) -> [u8; 32] {
+        self.prefix
+    }
+}
+
+impl<H1: StorageHasher, H2: StorageHasher> StorageDoubleMap<H1, H2> {
+    pub fn key<K1: Encode, K2: Encode>(
+        &self,
+        key1: &K1,
+        key2: &K2,
+    ) -> Result<Encoded, PolymeshRuntimeErr> {
+        Ok(StorageKey::new(&self.prefix)
+            .push::<H1, K1>(key1)?
+            .push::<H2, K2>(key2)?
+            .into())
+    }
+}
+
+/// Key builder for a storage n-map.  The hasher for each key is given when it is pushed.
+///
+/// ```ignore
+/// let key = map.key().push::<Blake2_128Concat, _>(&did)?.push::<Twox64Concat, _>(&id)?;
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StorageNMap {
+    prefix: [u8; 32],
+}
+
+impl StorageNMap {
+    pub const fn new(pallet: &str, item: &str) -> Self {
+        Self::from_prefix(storage_prefix(pallet, item))
+    }
+
+    pub const fn from_prefix(prefix: [u8; 32]) -> Self {
+        Self { prefix }
+    }
+
+    pub fn prefix(&self) -> [u8; 32] {
+        self.prefix
+    }
+
+    pub fn key(&self) -> StorageKey {
+        StorageKey::new(&self.prefix)
+    }
+}
+
+/// Iterator over the raw storage keys starting with a prefix.
+///
+/// At most `limit` keys are returned, to keep the gas used bounded.
+/// Use `last_key` as `start_after` to get the next page.
+pub struct StorageKeyIter {
+    prefix: Vec<u8>,
+    last_key: Option<Vec<u8>>,
+    remaining: u32,
+    done: bool,
+}
+
+impl StorageKeyIter {
+    pub fn new(prefix: Vec<u8>, start_after: Option<Vec<u8>>, limit: u32) -> Self {
+        Self {
+            prefix,
+            last_key: start_after,
+            remaining: limit,
+            done: false,
+        }
+    }
+
+    /// The last key returned, or `start_after` if no keys have been returned yet.
+    pub fn last_key(&self) -> Option<&[u8]> {
+        self.last_key.as_deref()
+    }
+
+    /// Returns `true` if there are no more keys with the prefix.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+}
+
+impl Iterator for StorageKeyIter {
+    type Item = Result<Vec<u8>, PolymeshRuntimeErr>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        match new_instance().next_storage_key(self.prefix.clone(), self.last_key.clone()) {
+            Ok(Some(key)) => {
+                self.last_key = Some(key.clone());
+                Some(Ok(key))
+            }
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+/// Iterator over the decoded keys of a `StorageMap`.
+pub struct StorageMapKeys<H, K> {
+    iter: StorageKeyIter,
+    _key: PhantomData<(H, K)>,
+}
+
+impl<H, K> StorageMapKeys<H, K> {
+    /// The last raw key returned.  Use as `start_after` to get the next page.
+    pub fn last_key(&self) -> Option<&[u8]> {
+        self.iter.last_key()
+    }
+
+    /// Returns `true` if there are no more keys in the map.
+    pub fn is_done(&self) -> bool {
+        self.iter.is_done()
+    }
+}
+
+impl<H: ReversibleStorageHasher, K: Decode> Iterator for StorageMapKeys<H, K> {
+    type Item = Result<K, PolymeshRuntimeErr>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = match self.iter.next()? {
+            Ok(key) => key,
+            Err(err) => return Some(Err(err)),
+        };
+        Some(decode_map_key::<H, K>(&key))
+    }
+}
+
+/// Decode the map key from a raw storage key, skipping the prefix and the hash.
+fn decode_map_key<H: ReversibleStorageHasher, K: Decode>(
+    key: &[u8],
+) -> Result<K, PolymeshRuntimeErr> {
+    key.get(32 + H::HASH_LEN..)
+        .and_then(|mut key| K::decode_all(&mut key).ok())
+        .ok_or(PolymeshRuntimeErr::DecodeFailed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockRuntime;
+
+    // Short prefix and keys, the mock can't return keys longer than `MAX_OUTPUT_LEN`.
+    const PREFIX: &[u8] = &[1, 1];
+
+    fn key(suffix: &[u8]) -> Vec<u8> {
+        let mut key = PREFIX.to_vec();
+        key.extend_from_slice(suffix);
+        key
+    }
+
+    fn register(keys: &[Vec<u8>]) -> MockRuntime {
+        let mock = MockRuntime::new();
+        mock.register();
+        for key in keys {
+            mock.insert_storage(key.clone(), vec![0]);
+        }
+        mock
+    }
+
+    #[ink_lang::test]
+    fn limit_stops_early() {
+        register(&[key(&[1]), key(&[2]), key(&[3])]);
+        let mut iter = StorageKeyIter::new(PREFIX.to_vec(), None, 2);
+        let keys = iter.by_ref().collect::<Result<Vec<_>, _>>();
+        assert_eq!(keys, Ok(vec![key(&[1]), key(&[2])]));
+        assert!(!iter.is_done());
+        assert_eq!(iter.last_key(), Some(&key(&[2])[..]));
+    }
+
+    #[ink_lang::test]
+    fn last_key_resumes() {
+        register(&[key(&[1]), key(&[2]), key(&[3])]);
+        let mut page = StorageKeyIter::new(PREFIX.to_vec(), None, 2);
+        assert_eq!(page.by_ref().count(), 2);
+
+        let start_after = page.last_key().map(|key| key.to_vec());
+        let mut page = StorageKeyIter::new(PREFIX.to_vec(), start_after, 2);
+        let keys = page.by_ref().collect::<Result<Vec<_>, _>>();
+        assert_eq!(keys, Ok(vec![key(&[3])]));
+        assert!(page.is_done());
+    }
+
+    #[ink_lang::test]
+    fn keys_outside_prefix_are_skipped() {
+        register(&[vec![1, 0, 9], key(&[1]), vec![1, 2, 0]]);
+        let mut iter = StorageKeyIter::new(PREFIX.to_vec(), None, 10);
+        let keys = iter.by_ref().collect::<Result<Vec<_>, _>>();
+        assert_eq!(keys, Ok(vec![key(&[1])]));
+        assert!(iter.is_done());
+    }
+
+    #[ink_lang::test]
+    fn start_after_before_prefix() {
+        register(&[vec![1, 0, 9], key(&[1])]);
+        let mut iter = StorageKeyIter::new(PREFIX.to_vec(), Some(vec![1, 0]), 10);
+        let keys = iter.by_ref().collect::<Result<Vec<_>, _>>();
+        assert_eq!(keys, Ok(vec![key(&[1])]));
+    }
+
+    #[ink_lang::test]
+    fn map_key_skips_the_hash() {
+        let map = StorageMap::<Blake2_128Concat>::from_prefix([1; 32]);
+        let key = map.key(&42u32).unwrap();
+        assert_eq!(key.0.len(), 32 + Blake2_128Concat::HASH_LEN + 4);
+        assert_eq!(decode_map_key::<Blake2_128Concat, u32>(&key.0), Ok(42));
+        assert_eq!(
+            decode_map_key::<Blake2_128Concat, u32>(&key.0[..40]),
+            Err(PolymeshRuntimeErr::DecodeFailed)
+        );
+        // Extra bytes after the key.
+        let mut long_key = key.0.clone();
+        long_key.push(0);
+        assert_eq!(
+            decode_map_key::<Blake2_128Concat, u32>(&long_key),
+            Err(PolymeshRuntimeErr::DecodeFailed)
+        );
+    }
+}
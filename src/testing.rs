@@ -0,0 +1,669 @@
+//! Off-chain mock of the Polymesh chain extension for `#[ink::test]` unit tests.
+//!
+//! ```ignore
+//! let mock = MockRuntime::new();
+//! mock.register();
+//! mock.set_key_did(caller, [1; 32]);
+//! // ... call contract messages ...
+//! assert_eq!(mock.calls().len(), 1);
+//! ```
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Bound;
+use std::rc::Rc;
+
+use ink_env::AccountId;
+use scale::{Decode, Encode};
+
+use crate::func_ids::*;
+use crate::{
+    BatchError, CallIndex, CallInfo, DispatchClass, DispatchResult, KeyRecord, Pays,
+    PermissionCheck, PolymeshRuntimeErr, RuntimeVersion, SecondaryKey, Weight,
+};
+
+/// Longest response the `ink_env` 3.0 off-chain engine can return from a chain extension.
+///
+/// The engine copies the encoded `(status, output)` into a 32 byte buffer.
+pub const MAX_OUTPUT_LEN: usize = 27;
+
+/// Chain extension version returned by the mock.
+pub const MOCK_EXTENSION_VERSION: u32 = 1;
+
+struct MockState {
+    storage: BTreeMap<Vec<u8>, Vec<u8>>,
+    key_dids: BTreeMap<AccountId, [u8; 32]>,
+    key_records: BTreeMap<AccountId, KeyRecord>,
+    calls: Vec<Vec<u8>>,
+    batches: Vec<Vec<u8>>,
+    call_result: DispatchResult,
+    /// Index of the failing call in batches, when `call_result` is an error.
+    batch_error_index: u32,
+    call_weight: Weight,
+    runtime_version: RuntimeVersion,
+    /// Valid `(signature, public, message)`.
+    signatures: BTreeSet<(Vec<u8>, Vec<u8>, Vec<u8>)>,
+    ecdsa_keys: BTreeMap<([u8; 65], [u8; 32]), [u8; 33]>,
+    call_indices: BTreeMap<(Vec<u8>, Vec<u8>), CallIndex>,
+    extension_version: u32,
+    /// Functions that return `UnknownFunctionId`, to mock older runtimes.
+    disabled: BTreeSet<u32>,
+    /// Errors returned by functions instead of their result.
+    errors: BTreeMap<u32, PolymeshRuntimeErr>,
+}
+
+impl Default for MockState {
+    fn default() -> Self {
+        Self {
+            storage: Default::default(),
+            key_dids: Default::default(),
+            key_records: Default::default(),
+            calls: Default::default(),
+            batches: Default::default(),
+            call_result: Ok(()),
+            batch_error_index: 0,
+            call_weight: 0,
+            runtime_version: Default::default(),
+            signatures: Default::default(),
+            ecdsa_keys: Default::default(),
+            call_indices: Default::default(),
+            extension_version: MOCK_EXTENSION_VERSION,
+            disabled: Default::default(),
+            errors: Default::default(),
+        }
+    }
+}
+
+impl MockState {
+    fn call(
+        &mut self,
+        func_id: u32,
+        input: &[u8],
+        output: &mut Vec<u8>,
+    ) -> Result<(), PolymeshRuntimeErr> {
+        if self.disabled.contains(&func_id) {
+            return Err(PolymeshRuntimeErr::UnknownFunctionId);
+        }
+        if let Some(err) = self.errors.get(&func_id) {
+            return Err(*err);
+        }
+        match func_id {
+            CALL_RUNTIME => {
+                self.calls.push(input.to_vec());
+                self.call_result
+                    .map_err(|_| PolymeshRuntimeErr::CallDispatchFailed)?;
+            }
+            READ_STORAGE => {
+                self.storage.get(input).encode_to(output);
+            }
+            READ_STORAGE_RANGE => {
+                let (offset, len) = decode::<(u32, u32)>(input)?;
+                let key = &input[8..];
+                self.storage
+                    .get(key)
+                    .map(|value| {
+                        let start = value.len().min(offset as usize);
+                        let end = value.len().min(start.saturating_add(len as usize));
+                        value[start..end].to_vec()
+                    })
+                    .encode_to(output);
+            }
+            STORAGE_LEN => {
+                self.storage
+                    .get(input)
+                    .map(|value| value.len() as u32)
+                    .encode_to(output);
+            }
+            NEXT_STORAGE_KEY => {
+                let (prefix, start_after) = decode::<(Vec<u8>, Option<Vec<u8>>)>(input)?;
+                // Start at the prefix if `start_after` sorts before it.
+                let start = match start_after {
+                    Some(key) if key >= prefix => Bound::Excluded(key),
+                    _ => Bound::Included(prefix.clone()),
+                };
+                self.storage
+                    .range((start, Bound::Unbounded))
+                    .next()
+                    .map(|(key, _)| key)
+                    .filter(|key| key.starts_with(&prefix))
+                    .encode_to(output);
+            }
+            READ_STORAGE_BATCH => {
+                let keys = decode::<Vec<Vec<u8>>>(input)?;
+                keys.iter()
+                    .map(|key| self.storage.get(key))
+                    .collect::<Vec<_>>()
+                    .encode_to(output);
+            }
+            GET_SPEC_VERSION => {
+                self.runtime_version.spec_version.encode_to(output);
+            }
+            GET_TRANSACTION_VERSION => {
+                self.runtime_version.transaction_version.encode_to(output);
+            }
+            GET_KEY_DID => {
+                let key = decode::<AccountId>(input)?;
+                self.key_dids.get(&key).encode_to(output);
+            }
+            CALL_RUNTIME_WITH_ERROR => {
+                self.calls.push(input.to_vec());
+                self.call_result.encode_to(output);
+            }
+            CALL_RUNTIME_WITH_INFO => {
+                self.calls.push(input.to_vec());
+                self.call_info().encode_to(output);
+            }
+            DRY_RUN_CALL => {
+                // Dry runs are not recorded, since they don't change state.
+                self.call_info().encode_to(output);
+            }
+            CALL_RUNTIME_BATCH => {
+                // The calls can't be split without decoding them, so record the whole batch.
+                self.batches.push(input.to_vec());
+                let index = self.batch_error_index;
+                self.call_result
+                    .map_err(|error| BatchError { index, error })
+                    .encode_to(output);
+            }
+            TWOX_64 => {
+                sp_core_hashing::twox_64(input).encode_to(output);
+            }
+            TWOX_128 => {
+                sp_core_hashing::twox_128(input).encode_to(output);
+            }
+            TWOX_256 => {
+                sp_core_hashing::twox_256(input).encode_to(output);
+            }
+            BLAKE2_128 => {
+                sp_core_hashing::blake2_128(input).encode_to(output);
+            }
+            BLAKE2_256 => {
+                sp_core_hashing::blake2_256(input).encode_to(output);
+            }
+            KECCAK_256 => {
+                sp_core_hashing::keccak_256(input).encode_to(output);
+            }
+            SHA2_256 => {
+                sp_core_hashing::sha2_256(input).encode_to(output);
+            }
+            SR25519_VERIFY | ED25519_VERIFY => {
+                self.verify(input, 64, 32).encode_to(output);
+            }
+            ECDSA_VERIFY => {
+                self.verify(input, 65, 33).encode_to(output);
+            }
+            ECDSA_RECOVER => {
+                let key = decode::<([u8; 65], [u8; 32])>(input)?;
+                self.ecdsa_keys.get(&key).encode_to(output);
+            }
+            GET_CALL_INDEX => {
+                let key = decode::<(Vec<u8>, Vec<u8>)>(input)?;
+                self.call_indices.get(&key).encode_to(output);
+            }
+            GET_RUNTIME_VERSION => {
+                self.runtime_version.encode_to(output);
+            }
+            GET_EXTENSION_VERSION => {
+                self.extension_version.encode_to(output);
+            }
+            SUPPORTED_FUNCTIONS => {
+                ALL.iter()
+                    .copied()
+                    .filter(|func_id| !self.disabled.contains(func_id))
+                    .collect::<Vec<u32>>()
+                    .encode_to(output);
+            }
+            GET_KEY_RECORD => {
+                let key = decode::<AccountId>(input)?;
+                self.key_records.get(&key).encode_to(output);
+            }
+            GET_PRIMARY_KEY => {
+                let did = decode::<[u8; 32]>(input)?;
+                self.key_records
+                    .iter()
+                    .find(|(_, record)| **record == KeyRecord::PrimaryKey(did))
+                    .map(|(key, _)| key)
+                    .encode_to(output);
+            }
+            GET_SECONDARY_KEYS => {
+                let (did, start_after, limit) =
+                    decode::<([u8; 32], Option<AccountId>, u32)>(input)?;
+                self.key_records
+                    .iter()
+                    .filter(|(key, _)| start_after.map_or(true, |start| **key > start))
+                    .filter_map(|(key, record)| match record {
+                        KeyRecord::SecondaryKey(key_did, permissions) if *key_did == did => {
+                            Some(SecondaryKey {
+                                key: *key,
+                                permissions: permissions.clone(),
+                            })
+                        }
+                        _ => None,
+                    })
+                    .take(limit as usize)
+                    .collect::<Vec<_>>()
+                    .encode_to(output);
+            }
+            CHECK_PERMISSIONS => {
+                let (key, check) = decode::<(AccountId, PermissionCheck)>(input)?;
+                let allowed = match self.key_records.get(&key) {
+                    Some(KeyRecord::PrimaryKey(_)) => true,
+                    Some(KeyRecord::SecondaryKey(_, perms)) => {
+                        perms.allows_extrinsic(&check.pallet_name, &check.dispatchable_name)
+                            && check.assets.iter().all(|ticker| perms.asset.allows(ticker))
+                            && check
+                                .portfolios
+                                .iter()
+                                .all(|portfolio| perms.portfolio.allows(portfolio))
+                    }
+                    _ => false,
+                };
+                allowed.encode_to(output);
+            }
+            _ => return Err(PolymeshRuntimeErr::UnknownFunctionId),
+        }
+        Ok(())
+    }
+
+    fn call_info(&self) -> CallInfo {
+        CallInfo {
+            result: self.call_result,
+            actual_weight: self.call_weight,
+            pays_fee: Pays::Yes,
+            class: DispatchClass::Normal,
+        }
+    }
+
+    fn verify(&self, input: &[u8], sig_len: usize, public_len: usize) -> bool {
+        if input.len() < sig_len + public_len {
+            return false;
+        }
+        let (signature, input) = input.split_at(sig_len);
+        let (public, message) = input.split_at(public_len);
+        self.signatures
+            .contains(&(signature.to_vec(), public.to_vec(), message.to_vec()))
+    }
+}
+
+fn decode<T: Decode>(mut input: &[u8]) -> Result<T, PolymeshRuntimeErr> {
+    T::decode(&mut input).map_err(|_| PolymeshRuntimeErr::Unknown)
+}
+
+/// Mock for a single chain extension function.
+struct MockFunction {
+    func_id: u32,
+    state: Rc<RefCell<MockState>>,
+}
+
+impl ink_env::test::ChainExtension for MockFunction {
+    fn func_id(&self) -> u32 {
+        self.func_id
+    }
+
+    fn call(&mut self, input: &[u8], output: &mut Vec<u8>) -> u32 {
+        // The off-chain engine encodes the input again, remove the extra length prefix.
+        let res = decode::<Vec<u8>>(input)
+            .and_then(|input| self.state.borrow_mut().call(self.func_id, &input, output));
+        // Fail here with a clear message, instead of in the engine.
+        assert!(
+            output.len() <= MAX_OUTPUT_LEN,
+            "MockRuntime: the {} byte response of function {:#010x} is longer than MAX_OUTPUT_LEN",
+            output.len(),
+            self.func_id
+        );
+        match res {
+            Ok(()) => 0,
+            Err(err) => err.code(),
+        }
+    }
+}
+
+/// Mock Polymesh runtime.  Cloning it shares the same state.
+///
+/// The off-chain engine can only return responses of at most `MAX_OUTPUT_LEN` bytes, larger
+/// responses panic.  That rules out `Some` identities, account ids, 32 byte hashes, runtime
+/// versions, key records and most lists of function ids or secondary keys.
+#[derive(Clone, Default)]
+pub struct MockRuntime {
+    state: Rc<RefCell<MockState>>,
+}
+
+impl MockRuntime {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register mocks for all `PolymeshRuntime` functions with the off-chain environment.
+    pub fn register(&self) {
+        for func_id in ALL {
+            ink_env::test::register_chain_extension(MockFunction {
+                func_id: *func_id,
+                state: self.state.clone(),
+            });
+        }
+    }
+
+    /// Set a storage value for `read_storage`.
+    pub fn insert_storage(&self, key: Vec<u8>, value: Vec<u8>) {
+        self.state.borrow_mut().storage.insert(key, value);
+    }
+
+    /// Remove a storage value.
+    pub fn remove_storage(&self, key: &[u8]) {
+        self.state.borrow_mut().storage.remove(key);
+    }
+
+    /// Set the identity returned by `get_key_did` for `key`.
+    pub fn set_key_did(&self, key: AccountId, did: [u8; 32]) {
+        self.state.borrow_mut().key_dids.insert(key, did);
+    }
+
+    /// Set the record returned by `get_key_record` for `key`.
+    /// Also used for `get_key_did`, `get_primary_key` and `get_secondary_keys`.
+    pub fn set_key_record(&self, key: AccountId, record: KeyRecord) {
+        let mut state = self.state.borrow_mut();
+        if let Some(did) = record.did() {
+            state.key_dids.insert(key, did);
+        }
+        state.key_records.insert(key, record);
+    }
+
+    /// Set the result of all following runtime calls.
+    pub fn set_call_result(&self, res: DispatchResult) {
+        self.state.borrow_mut().call_result = res;
+    }
+
+    /// Set the index of the failing call reported by `call_runtime_batch`.
+    /// The batch only fails if the call result is an error.
+    pub fn set_batch_error_index(&self, index: u32) {
+        self.state.borrow_mut().batch_error_index = index;
+    }
+
+    /// Set the actual weight reported for all following runtime calls.
+    pub fn set_call_weight(&self, weight: Weight) {
+        self.state.borrow_mut().call_weight = weight;
+    }
+
+    /// Set the runtime version.  Also used for `get_spec_version` and `get_transaction_version`.
+    pub fn set_runtime_version(&self, version: RuntimeVersion) {
+        self.state.borrow_mut().runtime_version = version;
+    }
+
+    pub fn set_spec_version(&self, version: u32) {
+        self.state.borrow_mut().runtime_version.spec_version = version;
+    }
+
+    pub fn set_transaction_version(&self, version: u32) {
+        self.state.borrow_mut().runtime_version.transaction_version = version;
+    }
+
+    /// Mark a signature as valid for the `*_verify` functions.
+    pub fn add_valid_signature(&self, signature: &[u8], public: &[u8], message: &[u8]) {
+        self.state.borrow_mut().signatures.insert((
+            signature.to_vec(),
+            public.to_vec(),
+            message.to_vec(),
+        ));
+    }
+
+    /// Set the public key returned by `ecdsa_recover`.
+    pub fn set_ecdsa_recover(&self, signature: [u8; 65], message_hash: [u8; 32], public: [u8; 33]) {
+        self.state
+            .borrow_mut()
+            .ecdsa_keys
+            .insert((signature, message_hash), public);
+    }
+
+    /// Set the call index returned by `get_call_index` for `pallet.call`.
+    pub fn set_call_index(&self, pallet: &str, call: &str, pallet_index: u8, call_index: u8) {
+        self.state.borrow_mut().call_indices.insert(
+            (pallet.as_bytes().to_vec(), call.as_bytes().to_vec()),
+            CallIndex {
+                pallet_index,
+                call_index,
+            },
+        );
+    }
+
+    pub fn set_extension_version(&self, version: u32) {
+        self.state.borrow_mut().extension_version = version;
+    }
+
+    /// Make `func_id` return `UnknownFunctionId`, to mock older runtimes.
+    pub fn disable_function(&self, func_id: u32) {
+        self.state.borrow_mut().disabled.insert(func_id);
+    }
+
+    /// Make `func_id` return `err`.
+    pub fn set_function_error(&self, func_id: u32, err: PolymeshRuntimeErr) {
+        self.state.borrow_mut().errors.insert(func_id, err);
+    }
+
+    /// All encoded calls passed to `call_runtime`, in order.
+    pub fn calls(&self) -> Vec<Vec<u8>> {
+        self.state.borrow().calls.clone()
+    }
+
+    /// All encoded batches passed to `call_runtime_batch`, in order.
+    /// Each batch is encoded as `Vec<Call>`.
+    pub fn batches(&self) -> Vec<Vec<u8>> {
+        self.state.borrow().batches.clone()
+    }
+
+    /// Call a mocked function without the off-chain engine, for responses longer than `MAX_OUTPUT_LEN`.
+    #[cfg(test)]
+    pub(crate) fn call_direct(
+        &self,
+        func_id: u32,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PolymeshRuntimeErr> {
+        let mut output = Vec::new();
+        self.state.borrow_mut().call(func_id, input, &mut output)?;
+        Ok(output)
+    }
+
+    /// Clear the recorded runtime calls and batches.
+    pub fn clear_calls(&self) {
+        let mut state = self.state.borrow_mut();
+        state.calls.clear();
+        state.batches.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{new_instance, DispatchError, Encoded};
+
+    fn register() -> MockRuntime {
+        let mock = MockRuntime::new();
+        mock.register();
+        mock
+    }
+
+    #[test]
+    fn all_functions_are_mocked() {
+        let mock = MockRuntime::new();
+        for func_id in ALL {
+            // Most functions fail to decode the empty input, but they must be known.
+            let res = mock.call_direct(*func_id, &[]);
+            assert_ne!(
+                res,
+                Err(PolymeshRuntimeErr::UnknownFunctionId),
+                "{:#010x}",
+                func_id
+            );
+        }
+    }
+
+    #[ink_lang::test]
+    fn read_storage() {
+        let mock = register();
+        mock.insert_storage(vec![1, 2, 3], vec![4, 5, 6]);
+        assert_eq!(
+            new_instance().read_storage(Encoded(vec![1, 2, 3])),
+            Ok(Some(vec![4, 5, 6]))
+        );
+        assert_eq!(new_instance().read_storage(Encoded(vec![1, 2])), Ok(None));
+        mock.remove_storage(&[1, 2, 3]);
+        assert_eq!(
+            new_instance().read_storage(Encoded(vec![1, 2, 3])),
+            Ok(None)
+        );
+    }
+
+    #[ink_lang::test]
+    fn read_storage_range() {
+        let mock = register();
+        mock.insert_storage(vec![1], (1..=10).collect());
+        let range = |offset, len, key: &[u8]| {
+            new_instance().read_storage_range(offset, len, Encoded(key.to_vec()))
+        };
+        assert_eq!(range(2, 3, &[1]), Ok(Some(vec![3, 4, 5])));
+        // `len` past the end.
+        assert_eq!(range(8, 5, &[1]), Ok(Some(vec![9, 10])));
+        // `offset` past the end.
+        assert_eq!(range(20, 5, &[1]), Ok(Some(vec![])));
+        assert_eq!(range(0, 5, &[2]), Ok(None));
+    }
+
+    #[ink_lang::test]
+    fn storage_len() {
+        let mock = register();
+        mock.insert_storage(vec![1], vec![0; 100]);
+        assert_eq!(new_instance().storage_len(Encoded(vec![1])), Ok(Some(100)));
+        assert_eq!(new_instance().storage_len(Encoded(vec![2])), Ok(None));
+    }
+
+    #[ink_lang::test]
+    fn call_runtime_is_recorded() {
+        let mock = register();
+        assert_eq!(new_instance().call_runtime(Encoded(vec![7, 1, 42])), Ok(()));
+        assert_eq!(new_instance().call_runtime(Encoded(vec![7, 2])), Ok(()));
+        assert_eq!(mock.calls(), vec![vec![7, 1, 42], vec![7, 2]]);
+        mock.clear_calls();
+        assert!(mock.calls().is_empty());
+    }
+
+    #[ink_lang::test]
+    fn get_key_did() {
+        let mock = register();
+        let key = AccountId::from([1; 32]);
+        mock.set_key_did(key, [2; 32]);
+        assert_eq!(
+            new_instance().get_key_did(AccountId::from([3; 32])),
+            Ok(None)
+        );
+        // `Some(did)` is longer than `MAX_OUTPUT_LEN`.
+        let output = mock.call_direct(GET_KEY_DID, &key.encode()).unwrap();
+        let did = <Option<[u8; 32]>>::decode(&mut &output[..]).unwrap();
+        assert_eq!(did, Some([2; 32]));
+    }
+
+    #[ink_lang::test]
+    #[should_panic(expected = "MAX_OUTPUT_LEN")]
+    fn long_response_panics() {
+        let mock = register();
+        let key = AccountId::from([1; 32]);
+        mock.set_key_did(key, [2; 32]);
+        let _ = new_instance().get_key_did(key);
+    }
+
+    #[ink_lang::test]
+    fn failing_call() {
+        let mock = register();
+        mock.set_call_result(Err(DispatchError::BadOrigin));
+        assert_eq!(
+            new_instance().call_runtime(Encoded(vec![7, 1])),
+            Err(PolymeshRuntimeErr::CallDispatchFailed)
+        );
+        assert_eq!(
+            new_instance().call_runtime_with_error(Encoded(vec![7, 1])),
+            Ok(Err(DispatchError::BadOrigin))
+        );
+        // Failed calls are still recorded.
+        assert_eq!(mock.calls().len(), 2);
+    }
+
+    #[ink_lang::test]
+    fn call_runtime_batch() {
+        let mock = register();
+        let calls = || {
+            vec![
+                Encoded(vec![7, 1]),
+                Encoded(vec![7, 2]),
+                Encoded(vec![8, 1]),
+            ]
+        };
+        assert_eq!(new_instance().call_runtime_batch(calls()), Ok(Ok(())));
+
+        mock.set_call_result(Err(DispatchError::BadOrigin));
+        mock.set_batch_error_index(2);
+        assert_eq!(
+            new_instance().call_runtime_batch(calls()),
+            Ok(Err(BatchError {
+                index: 2,
+                error: DispatchError::BadOrigin,
+            }))
+        );
+        // Batches are recorded as `Vec<Call>`, but not as single calls.
+        assert_eq!(mock.batches(), vec![vec![12, 7, 1, 7, 2, 8, 1]; 2]);
+        assert!(mock.calls().is_empty());
+    }
+
+    fn key(n: u8) -> AccountId {
+        AccountId::from([n; 32])
+    }
+
+    /// Identity `[1; 32]` with primary key `10` and secondary keys `11..=13`.
+    /// Key `14` is a secondary key of another identity.
+    fn identity_keys() -> MockRuntime {
+        let mock = MockRuntime::new();
+        let permissions = crate::Permissions {
+            asset: crate::SubsetRestriction::Whole,
+            extrinsic: crate::SubsetRestriction::Whole,
+            portfolio: crate::SubsetRestriction::Whole,
+        };
+        mock.set_key_record(key(10), KeyRecord::PrimaryKey([1; 32]));
+        for n in [13, 11, 12] {
+            mock.set_key_record(
+                key(n),
+                KeyRecord::SecondaryKey([1; 32], permissions.clone()),
+            );
+        }
+        mock.set_key_record(key(14), KeyRecord::SecondaryKey([2; 32], permissions));
+        mock
+    }
+
+    // Account ids are longer than `MAX_OUTPUT_LEN`, so these call the mock directly.
+
+    #[test]
+    fn get_primary_key() {
+        let mock = identity_keys();
+        let primary_key = |did: [u8; 32]| {
+            let output = mock.call_direct(GET_PRIMARY_KEY, &did.encode()).unwrap();
+            <Option<AccountId>>::decode(&mut &output[..]).unwrap()
+        };
+        assert_eq!(primary_key([1; 32]), Some(key(10)));
+        assert_eq!(primary_key([2; 32]), None);
+    }
+
+    #[test]
+    fn get_secondary_keys() {
+        let mock = identity_keys();
+        let secondary_keys = |start_after: Option<AccountId>, limit: u32| {
+            let input = ([1u8; 32], start_after, limit).encode();
+            let output = mock.call_direct(GET_SECONDARY_KEYS, &input).unwrap();
+            <Vec<SecondaryKey>>::decode(&mut &output[..])
+                .unwrap()
+                .into_iter()
+                .map(|secondary_key| secondary_key.key)
+                .collect::<Vec<_>>()
+        };
+        // Only the identity's secondary keys, ordered by key.
+        assert_eq!(secondary_keys(None, 10), vec![key(11), key(12), key(13)]);
+        assert_eq!(secondary_keys(None, 2), vec![key(11), key(12)]);
+        assert_eq!(secondary_keys(Some(key(12)), 10), vec![key(13)]);
+        assert_eq!(secondary_keys(Some(key(13)), 10), vec![]);
+    }
+}
@@ -0,0 +1,350 @@
+//! Types mirroring the SCALE encoding of runtime types returned by the chain extension.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use ink_env::AccountId;
+
+#[cfg(feature = "std")]
+use scale_info::TypeInfo;
+
+/// Mirrors `sp_runtime::DispatchError`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub enum DispatchError {
+    /// Some error occurred.  The runtime doesn't encode the message.
+    Other,
+    /// Failed to lookup some data.
+    CannotLookup,
+    /// A bad origin.
+    BadOrigin,
+    /// A custom error in a module (pallet).
+    Module(ModuleError),
+    /// At least one consumer is remaining so the account cannot be destroyed.
+    ConsumerRemaining,
+    /// There are no providers so the account cannot be created.
+    NoProviders,
+    /// There are too many consumers so the account cannot be created.
+    TooManyConsumers,
+    /// An error to do with tokens.
+    Token(TokenError),
+    /// An arithmetic error.
+    Arithmetic(ArithmeticError),
+    /// The number of transactional layers has been reached, or we are not in a transactional layer.
+    Transactional(TransactionalError),
+}
+
+impl DispatchError {
+    /// Returns the module error, if this is a pallet error.
+    pub fn module_error(&self) -> Option<&ModuleError> {
+        match self {
+            Self::Module(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    /// Check if this is the error `error_index` from pallet `pallet_index`.
+    pub fn is_module_error(&self, pallet_index: u8, error_index: u8) -> bool {
+        self.module_error()
+            .map(|err| err.index == pallet_index && err.error_index() == error_index)
+            .unwrap_or(false)
+    }
+}
+
+/// Mirrors `sp_runtime::ModuleError`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub struct ModuleError {
+    /// Pallet index.
+    pub index: u8,
+    /// Encoded pallet error.  The first byte is the error index.
+    pub error: [u8; 4],
+}
+
+impl ModuleError {
+    /// The error's index in the pallet's `Error` enum.
+    pub fn error_index(&self) -> u8 {
+        self.error[0]
+    }
+}
+
+/// Mirrors `sp_runtime::TokenError`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub enum TokenError {
+    /// Funds are unavailable.
+    NoFunds,
+    /// Account that must exist would die.
+    WouldDie,
+    /// Account cannot exist with the funds that would be given.
+    BelowMinimum,
+    /// Account cannot be created.
+    CannotCreate,
+    /// The asset in question is unknown.
+    UnknownAsset,
+    /// Funds exist but are frozen.
+    Frozen,
+    /// Operation is not supported by the asset.
+    Unsupported,
+}
+
+/// Mirrors `sp_runtime::ArithmeticError`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub enum ArithmeticError {
+    /// Underflow.
+    Underflow,
+    /// Overflow.
+    Overflow,
+    /// Division by zero.
+    DivisionByZero,
+}
+
+/// Mirrors `sp_runtime::TransactionalError`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub enum TransactionalError {
+    /// Too many transactional layers have been spawned.
+    LimitReached,
+    /// A transactional layer was expected, but does not exist.
+    NoLayer,
+}
+
+/// Result of dispatching a runtime call.
+pub type DispatchResult = core::result::Result<(), DispatchError>;
+
+/// Weight of a dispatched call.
+pub type Weight = u64;
+
+/// Mirrors `frame_support::weights::Pays`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub enum Pays {
+    /// Transactor will pay related fees.
+    Yes,
+    /// Transactor will NOT pay related fees.
+    No,
+}
+
+/// Mirrors `frame_support::weights::DispatchClass`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub enum DispatchClass {
+    /// A normal dispatch.
+    Normal,
+    /// An operational dispatch.
+    Operational,
+    /// A mandatory dispatch.
+    Mandatory,
+}
+
+/// Outcome of a runtime call with its post-dispatch info.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub struct CallInfo {
+    /// Result of the call.
+    pub result: DispatchResult,
+    /// Actual weight used by the call.
+    pub actual_weight: Weight,
+    /// Were fees charged for the call.
+    pub pays_fee: Pays,
+    /// Dispatch class of the call.
+    pub class: DispatchClass,
+}
+
+/// The call at `index` in a batch failed.  None of the calls in the batch are committed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub struct BatchError {
+    /// Index of the first failing call.
+    pub index: u32,
+    /// Error from the failing call.
+    pub error: DispatchError,
+}
+
+/// Result of dispatching a batch of runtime calls.
+pub type BatchResult = core::result::Result<(), BatchError>;
+
+/// Pallet and call index of a runtime call.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub struct CallIndex {
+    pub pallet_index: u8,
+    pub call_index: u8,
+}
+
+/// Mirrors `sp_version::RuntimeVersion`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub struct RuntimeVersion {
+    /// Runtime name.  For example: `polymesh_mainnet` or `polymesh_testnet`.
+    pub spec_name: String,
+    /// Name of the runtime implementation.
+    pub impl_name: String,
+    pub authoring_version: u32,
+    pub spec_version: u32,
+    pub impl_version: u32,
+    /// Supported runtime APIs and their versions.
+    pub apis: Vec<([u8; 8], u32)>,
+    pub transaction_version: u32,
+    pub state_version: u8,
+}
+
+impl RuntimeVersion {
+    /// Get the version of runtime API `id`, if the runtime supports it.
+    pub fn api_version(&self, id: &[u8; 8]) -> Option<u32> {
+        self.apis
+            .iter()
+            .find(|(api, _)| api == id)
+            .map(|(_, version)| *version)
+    }
+}
+
+/// Mirrors `polymesh_primitives::SubsetRestriction`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub enum SubsetRestriction<T> {
+    /// No restrictions.
+    Whole,
+    /// Only these items.
+    These(Vec<T>),
+    /// All items except these.
+    Except(Vec<T>),
+}
+
+impl<T: PartialEq> SubsetRestriction<T> {
+    /// Check if `item` is allowed.
+    pub fn allows(&self, item: &T) -> bool {
+        match self {
+            Self::Whole => true,
+            Self::These(items) => items.contains(item),
+            Self::Except(items) => !items.contains(item),
+        }
+    }
+}
+
+/// Mirrors `polymesh_primitives::identity_id::PortfolioKind`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub enum PortfolioKind {
+    Default,
+    User(u64),
+}
+
+/// Mirrors `polymesh_primitives::identity_id::PortfolioId`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub struct PortfolioId {
+    pub did: [u8; 32],
+    pub kind: PortfolioKind,
+}
+
+/// Mirrors `polymesh_primitives::secondary_key::PalletPermissions`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub struct PalletPermissions {
+    pub pallet_name: Vec<u8>,
+    pub dispatchable_names: SubsetRestriction<Vec<u8>>,
+}
+
+/// Mirrors `polymesh_primitives::secondary_key::Permissions`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub struct Permissions {
+    /// Allowed asset tickers.
+    pub asset: SubsetRestriction<[u8; 12]>,
+    /// Allowed pallets and extrinsics.
+    pub extrinsic: SubsetRestriction<PalletPermissions>,
+    /// Allowed portfolios.
+    pub portfolio: SubsetRestriction<PortfolioId>,
+}
+
+impl Permissions {
+    /// Check if the extrinsic `pallet_name.dispatchable_name` is allowed.
+    pub fn allows_extrinsic(&self, pallet_name: &[u8], dispatchable_name: &[u8]) -> bool {
+        let covers = |perms: &PalletPermissions| {
+            perms.pallet_name == pallet_name
+                && perms.dispatchable_names.allows(&dispatchable_name.to_vec())
+        };
+        match &self.extrinsic {
+            SubsetRestriction::Whole => true,
+            SubsetRestriction::These(pallets) => pallets.iter().any(covers),
+            SubsetRestriction::Except(pallets) => !pallets.iter().any(covers),
+        }
+    }
+}
+
+/// The extrinsic, assets and portfolios a key needs permission for.  Passed to `check_permissions`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub struct PermissionCheck {
+    pub pallet_name: Vec<u8>,
+    pub dispatchable_name: Vec<u8>,
+    pub assets: Vec<[u8; 12]>,
+    pub portfolios: Vec<PortfolioId>,
+}
+
+impl PermissionCheck {
+    pub fn new(pallet_name: &str, dispatchable_name: &str) -> Self {
+        Self {
+            pallet_name: pallet_name.as_bytes().to_vec(),
+            dispatchable_name: dispatchable_name.as_bytes().to_vec(),
+            assets: Vec::new(),
+            portfolios: Vec::new(),
+        }
+    }
+
+    /// Also require permission for the asset `ticker`.
+    pub fn asset(mut self, ticker: [u8; 12]) -> Self {
+        self.assets.push(ticker);
+        self
+    }
+
+    /// Also require permission for `portfolio`.
+    pub fn portfolio(mut self, portfolio: PortfolioId) -> Self {
+        self.portfolios.push(portfolio);
+        self
+    }
+}
+
+/// Mirrors `polymesh_primitives::secondary_key::KeyRecord`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub enum KeyRecord {
+    /// Primary key of the identity.
+    PrimaryKey([u8; 32]),
+    /// Secondary key of the identity, with its permissions.
+    SecondaryKey([u8; 32], Permissions),
+    /// Signer of the MultiSig account.
+    MultiSigSignerKey(AccountId),
+}
+
+impl KeyRecord {
+    /// The key's identity.  `None` for MultiSig signers.
+    pub fn did(&self) -> Option<[u8; 32]> {
+        match self {
+            Self::PrimaryKey(did) | Self::SecondaryKey(did, _) => Some(*did),
+            Self::MultiSigSignerKey(_) => None,
+        }
+    }
+
+    pub fn is_primary_key(&self) -> bool {
+        matches!(self, Self::PrimaryKey(_))
+    }
+
+    /// The secondary key's permissions.
+    pub fn permissions(&self) -> Option<&Permissions> {
+        match self {
+            Self::SecondaryKey(_, perms) => Some(perms),
+            _ => None,
+        }
+    }
+}
+
+/// Mirrors `polymesh_primitives::secondary_key::SecondaryKey`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub struct SecondaryKey {
+    pub key: AccountId,
+    pub permissions: Permissions,
+}